@@ -1,24 +1,133 @@
+use indexmap::IndexMap;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArazzoDocument {
     pub info: Info,
+    pub source_descriptions: Option<Vec<SourceDescription>>,
+    pub components: Option<Components>,
     pub workflows: Vec<Workflow>,
 }
 
+impl ArazzoDocument {
+    /// The parameter a step passes. A reusable one is the component it references
+    /// under `components.parameters`, with its `value` overridden when the step
+    /// gives one, or `None` when no such component exists.
+    pub fn resolve_parameter(&self, parameter: &StepParameter) -> Option<Parameter> {
+        let reusable = match parameter {
+            StepParameter::Parameter(parameter) => return Some(parameter.clone()),
+            StepParameter::Reusable(reusable) => reusable,
+        };
+        let name = reusable.reference.strip_prefix("$components.parameters.")?;
+        let component = self.components.as_ref()?.parameters.as_ref()?.get(name)?;
+
+        Some(Parameter {
+            value: reusable.value.clone().unwrap_or_else(|| component.value.clone()),
+            ..component.clone()
+        })
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Info {
     pub title: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceDescription {
+    pub name: String,
+    pub url: String,
+    #[serde(rename = "type")]
+    pub source_type: Option<SourceDescriptionType>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceDescriptionType {
+    OpenApi,
+    Arazzo,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Workflow {
     pub workflow_id: String,
+    pub summary: Option<String>,
     pub description: Option<String>,
+    pub inputs: Option<serde_json::Value>,
+    pub depends_on: Option<Vec<String>>,
     pub steps: Vec<Step>,
+    pub outputs: Option<IndexMap<String, String>>,
+}
+
+impl Workflow {
+    /// Where the workflow carries on after the step at `index` succeeded or failed,
+    /// `action` being the first of its actions whose criteria held. `retries` counts
+    /// how often `action` has already retried the step; without a `retryLimit` it
+    /// retries once.
+    pub fn transition<'a>(
+        &'a self,
+        index: usize,
+        succeeded: bool,
+        action: Option<&'a Action>,
+        retries: u32,
+    ) -> Transition<'a> {
+        let Some(action) = action else {
+            return match succeeded {
+                true if index + 1 < self.steps.len() => Transition::Step(index + 1),
+                true => Transition::End,
+                false => Transition::Failed,
+            };
+        };
+        if action.action_type == ActionType::End {
+            return Transition::End;
+        }
+        let step = match action.step_id.as_deref() {
+            Some(step_id) => match self.steps.iter().position(|step| step.step_id == step_id) {
+                Some(index) => Some(index),
+                None => return Transition::UnknownStep(step_id),
+            },
+            None => None,
+        };
+
+        match (&action.action_type, action.workflow_id.as_deref(), step) {
+            (ActionType::Retry, _, _) if retries >= action.retry_limit.unwrap_or(1) => {
+                Transition::RetryLimit
+            }
+            (ActionType::Retry, workflow, step) => Transition::Retry { step, workflow },
+            (_, Some(workflow_id), _) => Transition::Workflow(workflow_id),
+            (_, None, Some(step)) => Transition::Step(step),
+            (_, None, None) => Transition::End,
+        }
+    }
+}
+
+/// Where a workflow carries on after one of its steps.
+#[derive(Debug, PartialEq)]
+pub enum Transition<'a> {
+    /// The step at the given index runs next.
+    Step(usize),
+    /// The workflow hands over to another one.
+    Workflow(&'a str),
+    /// The step runs again, once the step at the given index or the named workflow,
+    /// if any, has run.
+    Retry {
+        step: Option<usize>,
+        workflow: Option<&'a str>,
+    },
+    End,
+    /// The failure is not handled by any action.
+    Failed,
+    /// The retry has used up its `retryLimit`.
+    RetryLimit,
+    /// An action names a step the workflow does not have.
+    UnknownStep(&'a str),
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,15 +135,97 @@ pub struct Workflow {
 pub struct Step {
     pub step_id: String,
     pub description: Option<String>,
+    pub operation_id: Option<String>,
+    pub operation_path: Option<String>,
+    pub workflow_id: Option<String>,
+    pub parameters: Option<Vec<StepParameter>>,
+    pub request_body: Option<RequestBody>,
     pub success_criteria: Option<Vec<Criteria>>,
     pub on_success: Option<Vec<Action>>,
     pub on_failure: Option<Vec<Action>>,
+    pub outputs: Option<IndexMap<String, String>>,
+}
+
+/// A parameter, or a reference to a reusable one under `components.parameters`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum StepParameter {
+    Parameter(Parameter),
+    Reusable(ReusableParameter),
+}
+
+impl StepParameter {
+    /// The parameter name, or the component name of a reusable parameter.
+    pub fn name(&self) -> &str {
+        match self {
+            StepParameter::Parameter(parameter) => &parameter.name,
+            StepParameter::Reusable(reusable) => {
+                reusable.reference.rsplit('.').next().unwrap_or_default()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Parameter {
+    pub name: String,
+    #[serde(rename = "in")]
+    pub location: Option<String>,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReusableParameter {
+    pub reference: String,
+    pub value: Option<serde_json::Value>,
+}
+
+/// Objects steps reuse through `$components.<type>.<name>` references.
+#[derive(Debug, Deserialize)]
+pub struct Components {
+    pub parameters: Option<IndexMap<String, Parameter>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub content_type: Option<String>,
+    pub payload: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Criteria {
+    pub context: Option<String>,
     pub condition: Option<String>,
+    #[serde(rename = "type")]
+    pub criteria_type: Option<CriteriaType>,
+}
+
+/// How a criterion's condition is evaluated: a plain name, or a criterion
+/// expression type object pinning the JSONPath or XPath version.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CriteriaType {
+    Name(CriteriaTypeName),
+    Expression(CriterionExpressionType),
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CriteriaTypeName {
+    Simple,
+    Regex,
+    JsonPath,
+    XPath,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CriterionExpressionType {
+    #[serde(rename = "type")]
+    pub name: CriteriaTypeName,
+    pub version: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,6 +236,8 @@ pub struct Action {
     pub action_type: ActionType,
     pub workflow_id: Option<String>,
     pub step_id: Option<String>,
+    pub retry_after: Option<f64>,
+    pub retry_limit: Option<u32>,
     pub criteria: Option<Vec<Criteria>>,
 }
 
@@ -53,4 +246,5 @@ pub struct Action {
 pub enum ActionType {
     Goto,
     End,
+    Retry,
 }
@@ -0,0 +1,173 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::arazzo::{ArazzoDocument, SourceDescriptionType, Workflow};
+
+/// An Arazzo document referenced through a `type: arazzo` source description.
+#[derive(Debug)]
+pub struct ArazzoSource {
+    pub name: String,
+    pub url: String,
+    pub document: ArazzoDocument,
+}
+
+/// The local Arazzo documents an Arazzo document composes. Their own Arazzo
+/// source descriptions are followed only to detect reference cycles.
+#[derive(Debug, Default)]
+pub struct ArazzoSources {
+    pub documents: Vec<ArazzoSource>,
+}
+
+#[derive(Debug)]
+pub enum CompositionError {
+    Io(String, io::Error),
+    Parse(String, yaml_serde::Error),
+    Cycle(Vec<String>),
+}
+
+impl Display for CompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompositionError::Io(url, error) => {
+                write!(f, "Failed to read source description {}: {}", url, error)
+            }
+            CompositionError::Parse(url, error) => {
+                write!(f, "Failed to parse source description {}: {}", url, error)
+            }
+            CompositionError::Cycle(urls) => {
+                write!(f, "Cyclic source descriptions: {}", urls.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for CompositionError {}
+
+impl ArazzoSources {
+    /// Loads the local `type: arazzo` source descriptions of the document at `path`,
+    /// or of a document read from stdin when `path` is `None`. Remote URLs are skipped.
+    pub fn load(
+        arazzo: &ArazzoDocument,
+        path: Option<&Path>,
+    ) -> Result<ArazzoSources, CompositionError> {
+        let mut chain = Vec::new();
+        let base_dir = match path {
+            Some(path) => {
+                let path = fs::canonicalize(path)
+                    .map_err(|error| CompositionError::Io(path.display().to_string(), error))?;
+                let base_dir = path.parent().map_or(PathBuf::from("."), Path::to_path_buf);
+                chain.push(path);
+                base_dir
+            }
+            None => PathBuf::from("."),
+        };
+
+        load_from(arazzo, &base_dir, &mut chain)
+    }
+
+    pub fn find_source(&self, name: &str) -> Option<&ArazzoSource> {
+        self.documents.iter().find(|source| source.name == name)
+    }
+
+    /// Finds the workflow a `$sourceDescriptions.<name>.<workflowId>` reference points at.
+    pub fn find_workflow(&self, reference: &str) -> Option<&Workflow> {
+        let (name, workflow_id) = parse_workflow_reference(reference)?;
+
+        self.find_source(name)?
+            .document
+            .workflows
+            .iter()
+            .find(|workflow| workflow.workflow_id == workflow_id)
+    }
+}
+
+fn load_from(
+    arazzo: &ArazzoDocument,
+    base_dir: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<ArazzoSources, CompositionError> {
+    let mut sources = ArazzoSources::default();
+
+    for source_description in arazzo.source_descriptions.iter().flatten() {
+        let url = &source_description.url;
+        if source_description.source_type != Some(SourceDescriptionType::Arazzo)
+            || url.starts_with("http://")
+            || url.starts_with("https://")
+        {
+            continue;
+        }
+
+        let path = fs::canonicalize(base_dir.join(url))
+            .map_err(|error| CompositionError::Io(url.clone(), error))?;
+        if chain.contains(&path) {
+            let mut cycle = chain.iter().map(|p| p.display().to_string()).collect::<Vec<_>>();
+            cycle.push(path.display().to_string());
+            return Err(CompositionError::Cycle(cycle));
+        }
+
+        let content =
+            fs::read_to_string(&path).map_err(|error| CompositionError::Io(url.clone(), error))?;
+        let document: ArazzoDocument = yaml_serde::from_str(&content)
+            .map_err(|error| CompositionError::Parse(url.clone(), error))?;
+
+        chain.push(path.clone());
+        load_from(&document, path.parent().unwrap_or(base_dir), chain)?;
+        chain.pop();
+
+        sources.documents.push(ArazzoSource {
+            name: source_description.name.clone(),
+            url: url.clone(),
+            document,
+        });
+    }
+
+    Ok(sources)
+}
+
+/// Splits `$sourceDescriptions.<name>.<workflowId>` into its name and workflow ID.
+pub fn parse_workflow_reference(reference: &str) -> Option<(&str, &str)> {
+    reference
+        .strip_prefix("$sourceDescriptions.")
+        .and_then(|v| v.split_once('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(file: &str) -> Result<ArazzoSources, CompositionError> {
+        let content = fs::read_to_string(file).unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+
+        ArazzoSources::load(&arazzo, Some(Path::new(file)))
+    }
+
+    #[test]
+    fn load_arazzo_source() {
+        let sources = load("fixtures/composition/main.yml").unwrap();
+
+        let actual = sources
+            .find_workflow("$sourceDescriptions.sharedFlows.login")
+            .map(|w| w.workflow_id.as_str());
+
+        assert_eq!(Some("login"), actual);
+    }
+
+    #[test]
+    fn load_unknown_workflow() {
+        let sources = load("fixtures/composition/main.yml").unwrap();
+
+        let actual = sources.find_workflow("$sourceDescriptions.sharedFlows.logout");
+
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn load_cyclic_sources() {
+        let actual = load("fixtures/composition/cycle-a.yml");
+
+        assert!(matches!(actual, Err(CompositionError::Cycle(urls)) if urls.len() == 3));
+    }
+}
@@ -0,0 +1,517 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::iter::Peekable;
+use std::str::FromStr;
+use std::vec;
+
+use serde_json::Value;
+
+use crate::arazzo::{ArazzoDocument, CriteriaTypeName};
+use crate::expression::{self, Expression, ExpressionError};
+
+/// A parsed Arazzo "simple" condition.
+#[derive(Debug, PartialEq)]
+pub enum Condition {
+    /// `null`, `true`, `false`, a number, or a single-quoted string
+    Literal(Value),
+    Expression(Expression),
+    Not(Box<Condition>),
+    Binary(Box<Condition>, Operator, Box<Condition>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConditionError {
+    Syntax(String, String),
+    Expression(ExpressionError),
+    Evaluation(String),
+}
+
+impl Display for ConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionError::Syntax(condition, reason) => {
+                write!(f, "invalid condition '{}': {}", condition, reason)
+            }
+            ConditionError::Expression(error) => write!(f, "{}", error),
+            ConditionError::Evaluation(reason) => {
+                write!(f, "failed to evaluate condition: {}", reason)
+            }
+        }
+    }
+}
+
+impl Error for ConditionError {}
+
+impl Operator {
+    fn precedence(self) -> u8 {
+        match self {
+            Operator::Or => 1,
+            Operator::And => 2,
+            _ => 3,
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Eq => write!(f, "=="),
+            Operator::Ne => write!(f, "!="),
+            Operator::Lt => write!(f, "<"),
+            Operator::Le => write!(f, "<="),
+            Operator::Gt => write!(f, ">"),
+            Operator::Ge => write!(f, ">="),
+            Operator::And => write!(f, "&&"),
+            Operator::Or => write!(f, "||"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Not,
+    Operator(Operator),
+    Literal(Value),
+    Expression(Expression),
+}
+
+impl FromStr for Condition {
+    type Err = ConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            condition: s,
+            tokens: tokenize(s)?.into_iter().peekable(),
+        };
+
+        let condition = parser.or()?;
+        if parser.tokens.peek().is_some() {
+            return Err(parser.error("unexpected token after the end of the condition"));
+        }
+
+        Ok(condition)
+    }
+}
+
+/// Writes the condition normalized: single spaces around operators, and
+/// parentheses only where precedence needs them.
+impl Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Literal(Value::String(text)) => write!(f, "'{}'", text.replace('\'', "''")),
+            Condition::Literal(value) => write!(f, "{}", value),
+            Condition::Expression(expression) => write!(f, "{}", expression),
+            Condition::Not(condition) => match condition.as_ref() {
+                Condition::Binary(..) => write!(f, "!({})", condition),
+                _ => write!(f, "!{}", condition),
+            },
+            Condition::Binary(left, operator, right) => {
+                write_operand(f, left, *operator, false)?;
+                write!(f, " {} ", operator)?;
+                write_operand(f, right, *operator, true)
+            }
+        }
+    }
+}
+
+fn write_operand(
+    f: &mut fmt::Formatter<'_>,
+    operand: &Condition,
+    parent: Operator,
+    is_right: bool,
+) -> fmt::Result {
+    let needs_group = match operand {
+        Condition::Binary(_, operator, _) => {
+            operator.precedence() < parent.precedence()
+                || (operator.precedence() == parent.precedence()
+                    && (is_right || parent.precedence() == 3))
+        }
+        _ => false,
+    };
+
+    if needs_group {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+impl Condition {
+    /// The runtime expressions the condition reads, in order.
+    pub fn expressions(&self) -> Vec<&Expression> {
+        match self {
+            Condition::Literal(_) => Vec::new(),
+            Condition::Expression(expression) => vec![expression],
+            Condition::Not(condition) => condition.expressions(),
+            Condition::Binary(left, _, right) => {
+                let mut expressions = left.expressions();
+                expressions.extend(right.expressions());
+                expressions
+            }
+        }
+    }
+
+    /// Evaluates the condition against a context document that mirrors the
+    /// expression paths, such as `{"statusCode": 200, "response": {"body": {...}}}`.
+    /// Expressions missing from the context read as `null`.
+    pub fn evaluate(&self, context: &Value) -> Result<bool, ConditionError> {
+        match self.value(context)? {
+            Value::Bool(value) => Ok(value),
+            value => Err(ConditionError::Evaluation(format!(
+                "'{}' is {}, not a boolean",
+                self, value
+            ))),
+        }
+    }
+
+    fn value(&self, context: &Value) -> Result<Value, ConditionError> {
+        match self {
+            Condition::Literal(value) => Ok(value.clone()),
+            Condition::Expression(expression) => Ok(context
+                .pointer(&expression.context_pointer())
+                .cloned()
+                .unwrap_or(Value::Null)),
+            Condition::Not(condition) => Ok(Value::Bool(!condition.evaluate(context)?)),
+            Condition::Binary(left, Operator::And, right) => {
+                Ok(Value::Bool(left.evaluate(context)? && right.evaluate(context)?))
+            }
+            Condition::Binary(left, Operator::Or, right) => {
+                Ok(Value::Bool(left.evaluate(context)? || right.evaluate(context)?))
+            }
+            Condition::Binary(left, operator, right) => {
+                compare(*operator, &left.value(context)?, &right.value(context)?).map(Value::Bool)
+            }
+        }
+    }
+}
+
+/// Evaluates every simple criterion in the document against `context`, one
+/// `workflowId.stepId: condition => result` line per criterion.
+pub fn evaluate_criteria(arazzo: &ArazzoDocument, context: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for workflow in &arazzo.workflows {
+        for step in &workflow.steps {
+            for criterion in expression::step_criteria(step) {
+                let Some(condition) = criterion.condition.as_deref() else {
+                    continue;
+                };
+                if expression::criteria_type_name(criterion) != CriteriaTypeName::Simple {
+                    continue;
+                }
+
+                let line = match condition.parse::<Condition>() {
+                    Ok(condition) => match condition.evaluate(context) {
+                        Ok(result) => format!("{condition} => {result}"),
+                        Err(error) => format!("{condition} => {error}"),
+                    },
+                    Err(error) => format!("{condition} => {error}"),
+                };
+                lines.push(format!("{}.{}: {}", workflow.workflow_id, step.step_id, line));
+            }
+        }
+    }
+
+    lines
+}
+
+fn compare(operator: Operator, left: &Value, right: &Value) -> Result<bool, ConditionError> {
+    let ordering = match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64().partial_cmp(&r.as_f64()),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    };
+
+    match operator {
+        Operator::Eq => Ok(equals(left, right)),
+        Operator::Ne => Ok(!equals(left, right)),
+        _ => {
+            let Some(ordering) = ordering else {
+                return Err(ConditionError::Evaluation(format!(
+                    "cannot compare {} with {}",
+                    left, right
+                )));
+            };
+            Ok(match operator {
+                Operator::Lt => ordering.is_lt(),
+                Operator::Le => ordering.is_le(),
+                Operator::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            })
+        }
+    }
+}
+
+/// JSON equality, except that numbers are compared by value, so `200 == 200.0`.
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(l), Some(r)) => l == r,
+        _ => left == right,
+    }
+}
+
+fn tokenize(condition: &str) -> Result<Vec<Token>, ConditionError> {
+    let syntax = |reason: String| ConditionError::Syntax(condition.to_string(), reason);
+    let mut tokens = Vec::new();
+    let mut rest = condition.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let two = rest.get(..2).unwrap_or_default();
+        let (token, length) = match (c, two) {
+            (_, "==") => (Token::Operator(Operator::Eq), 2),
+            (_, "!=") => (Token::Operator(Operator::Ne), 2),
+            (_, "<=") => (Token::Operator(Operator::Le), 2),
+            (_, ">=") => (Token::Operator(Operator::Ge), 2),
+            (_, "&&") => (Token::Operator(Operator::And), 2),
+            (_, "||") => (Token::Operator(Operator::Or), 2),
+            ('<', _) => (Token::Operator(Operator::Lt), 1),
+            ('>', _) => (Token::Operator(Operator::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('\'', _) => {
+                let (text, length) = string_literal(rest)
+                    .ok_or_else(|| syntax(String::from("unterminated string literal")))?;
+                (Token::Literal(Value::String(text)), length)
+            }
+            ('$', _) => {
+                let length = rest
+                    .find(|c: char| c.is_whitespace() || "=!<>&|()'\",".contains(c))
+                    .unwrap_or(rest.len());
+                let expression = rest[..length].parse().map_err(ConditionError::Expression)?;
+                (Token::Expression(expression), length)
+            }
+            _ => {
+                let length = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || ".+-_".contains(c)))
+                    .unwrap_or(rest.len())
+                    .max(c.len_utf8());
+                let word = &rest[..length];
+                let value = match word {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ => match serde_json::from_str::<serde_json::Number>(word) {
+                        Ok(number) => Value::Number(number),
+                        Err(_) => return Err(syntax(format!("unexpected '{word}'"))),
+                    },
+                };
+                (Token::Literal(value), length)
+            }
+        };
+
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a single-quoted string at the start of `text`, where `''` is a quote.
+/// Returns the string and how many bytes it spans.
+fn string_literal(text: &str) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c != '\'' {
+            value.push(c);
+        } else if chars.peek().is_some_and(|(_, next)| *next == '\'') {
+            chars.next();
+            value.push('\'');
+        } else {
+            return Some((value, i + 1));
+        }
+    }
+
+    None
+}
+
+/// Recursive descent over `||`, then `&&`, then comparisons, then `!` and groups.
+struct Parser<'a> {
+    condition: &'a str,
+    tokens: Peekable<vec::IntoIter<Token>>,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &str) -> ConditionError {
+        ConditionError::Syntax(self.condition.to_string(), reason.to_string())
+    }
+
+    /// Consumes the next token if it is one of `operators`.
+    fn next_operator(&mut self, operators: &[Operator]) -> Option<Operator> {
+        match self.tokens.peek() {
+            Some(Token::Operator(operator)) if operators.contains(operator) => {
+                let operator = *operator;
+                self.tokens.next();
+                Some(operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn or(&mut self) -> Result<Condition, ConditionError> {
+        let mut condition = self.and()?;
+        while let Some(operator) = self.next_operator(&[Operator::Or]) {
+            condition = Condition::Binary(Box::new(condition), operator, Box::new(self.and()?));
+        }
+
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, ConditionError> {
+        let mut condition = self.comparison()?;
+        while let Some(operator) = self.next_operator(&[Operator::And]) {
+            condition =
+                Condition::Binary(Box::new(condition), operator, Box::new(self.comparison()?));
+        }
+
+        Ok(condition)
+    }
+
+    fn comparison(&mut self) -> Result<Condition, ConditionError> {
+        let comparisons = [
+            Operator::Eq,
+            Operator::Ne,
+            Operator::Lt,
+            Operator::Le,
+            Operator::Gt,
+            Operator::Ge,
+        ];
+
+        let condition = self.unary()?;
+        match self.next_operator(&comparisons) {
+            Some(operator) => {
+                let right = self.unary()?;
+                if self.next_operator(&comparisons).is_some() {
+                    return Err(self.error("comparisons cannot be chained without parentheses"));
+                }
+                Ok(Condition::Binary(Box::new(condition), operator, Box::new(right)))
+            }
+            None => Ok(condition),
+        }
+    }
+
+    fn unary(&mut self) -> Result<Condition, ConditionError> {
+        let Some(token) = self.tokens.next() else {
+            return Err(self.error("unexpected end of condition"));
+        };
+
+        match token {
+            Token::Not => Ok(Condition::Not(Box::new(self.unary()?))),
+            Token::Open => {
+                let condition = self.or()?;
+                if self.tokens.next() != Some(Token::Close) {
+                    return Err(self.error("missing ')'"));
+                }
+                Ok(condition)
+            }
+            Token::Literal(value) => Ok(Condition::Literal(value)),
+            Token::Expression(expression) => Ok(Condition::Expression(expression)),
+            Token::Close | Token::Operator(_) => Err(self.error("expected a value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_condition() {
+        let actual = [
+            "$statusCode==200",
+            "($statusCode == 200) && ($response.body#/status == 'ok')",
+            "!($statusCode >= 400 || $statusCode < 200)",
+            "($statusCode == 200 || $statusCode == 201) && $response.body#/ok",
+            "$response.body#/name == 'it''s'",
+        ]
+        .map(|text| text.parse::<Condition>().unwrap().to_string());
+
+        let expected = [
+            "$statusCode == 200",
+            "$statusCode == 200 && $response.body#/status == 'ok'",
+            "!($statusCode >= 400 || $statusCode < 200)",
+            "($statusCode == 200 || $statusCode == 201) && $response.body#/ok",
+            "$response.body#/name == 'it''s'",
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_invalid_condition() {
+        let actual = [
+            "$statusCode ==",
+            "($statusCode == 200",
+            "$statusCode == 'ok",
+            "$statusCode == ok",
+            "1 < $statusCode < 3",
+            "$foo == 1",
+        ]
+        .map(|text| text.parse::<Condition>().is_err());
+
+        assert_eq!([true; 6], actual);
+    }
+
+    #[test]
+    fn evaluate_condition() {
+        let context = serde_json::json!({
+            "statusCode": 200,
+            "response": { "body": { "status": "ok", "items": [{ "id": 1 }] } },
+            "steps": { "stepFoo": { "outputs": { "count": 3.0 } } },
+        });
+
+        let actual = [
+            "$statusCode == 200 && $response.body#/status == 'ok'",
+            "$statusCode >= 400 || $response.body#/items/0/id != 1",
+            "!($steps.stepFoo.outputs.count < 3)",
+            "$response.body#/missing == null",
+        ]
+        .map(|text| text.parse::<Condition>().unwrap().evaluate(&context));
+
+        let expected = [Ok(true), Ok(false), Ok(true), Ok(true)];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn evaluate_fixture_criteria() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let context = serde_json::json!({ "statusCode": 201 });
+
+        let actual = evaluate_criteria(&arazzo, &context);
+
+        let expected = vec![
+            String::from("authorizeThenCapture.createAuthorizationStep: $statusCode == 201 => true"),
+            String::from("authorizeThenCapture.captureAuthorizationStep: $statusCode == 201 => true"),
+            String::from("authorizeThenCapture.reverseAuthorizationStep: $statusCode == 201 => true"),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn evaluate_type_mismatch() {
+        let context = serde_json::json!({ "statusCode": 200 });
+
+        let actual = "$statusCode < 'a'".parse::<Condition>().unwrap().evaluate(&context);
+
+        assert!(matches!(actual, Err(ConditionError::Evaluation(_))));
+    }
+}
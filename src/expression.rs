@@ -0,0 +1,786 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::arazzo::{
+    ArazzoDocument, Criteria, CriteriaType, CriteriaTypeName, Step, StepParameter, Workflow,
+};
+use crate::condition::Condition;
+
+/// A parsed Arazzo runtime expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Url,
+    Method,
+    StatusCode,
+    Request(Source),
+    Response(Source),
+    Inputs(Reference),
+    Outputs(Reference),
+    Steps {
+        step_id: String,
+        outputs: Option<Reference>,
+    },
+    Workflows {
+        workflow_id: String,
+        property: Option<WorkflowProperty>,
+    },
+    SourceDescriptions {
+        name: String,
+        reference: Option<String>,
+    },
+    Components {
+        component_type: String,
+        name: String,
+    },
+}
+
+/// The part of an HTTP request or response that `$request` or `$response` reads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Header(String),
+    Query(String),
+    Path(String),
+    Body(Option<String>),
+}
+
+/// A named input or output, optionally followed by a JSON pointer into its value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub name: String,
+    pub pointer: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkflowProperty {
+    Inputs(Reference),
+    Outputs(Reference),
+}
+
+/// A piece of a string with `{$...}` expressions embedded in it.
+#[derive(Debug, PartialEq)]
+pub enum TemplatePart {
+    Text(String),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    Syntax(String, &'static str),
+    UnclosedTemplate(String),
+}
+
+impl Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionError::Syntax(expression, reason) => {
+                write!(f, "invalid runtime expression '{}': {}", expression, reason)
+            }
+            ExpressionError::UnclosedTemplate(text) => {
+                write!(f, "unclosed embedded expression in '{}'", text)
+            }
+        }
+    }
+}
+
+impl Error for ExpressionError {}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let syntax = |reason| ExpressionError::Syntax(s.to_string(), reason);
+
+        let (path, mut pointer) = match s.split_once('#') {
+            Some((path, pointer)) => {
+                validate_pointer(pointer).map_err(syntax)?;
+                (path, Some(pointer.to_string()))
+            }
+            None => (s, None),
+        };
+        let Some(path) = path.strip_prefix('$') else {
+            return Err(syntax("expected '$'"));
+        };
+        let segments = path.split('.').collect::<Vec<&str>>();
+        if segments.contains(&"") {
+            return Err(syntax("empty name"));
+        }
+
+        let expression = match segments.as_slice() {
+            ["url"] => Expression::Url,
+            ["method"] => Expression::Method,
+            ["statusCode"] => Expression::StatusCode,
+            ["request", rest @ ..] => {
+                Expression::Request(source(rest, &mut pointer).map_err(syntax)?)
+            }
+            ["response", rest @ ..] => {
+                Expression::Response(source(rest, &mut pointer).map_err(syntax)?)
+            }
+            ["inputs", rest @ ..] if !rest.is_empty() => {
+                Expression::Inputs(reference(rest, &mut pointer))
+            }
+            ["outputs", rest @ ..] if !rest.is_empty() => {
+                Expression::Outputs(reference(rest, &mut pointer))
+            }
+            ["steps", step_id] => Expression::Steps {
+                step_id: step_id.to_string(),
+                outputs: None,
+            },
+            ["steps", step_id, "outputs", rest @ ..] if !rest.is_empty() => Expression::Steps {
+                step_id: step_id.to_string(),
+                outputs: Some(reference(rest, &mut pointer)),
+            },
+            ["steps", ..] => return Err(syntax("expected $steps.<stepId>.outputs.<name>")),
+            ["workflows", workflow_id] => Expression::Workflows {
+                workflow_id: workflow_id.to_string(),
+                property: None,
+            },
+            ["workflows", workflow_id, "inputs", rest @ ..] if !rest.is_empty() => {
+                Expression::Workflows {
+                    workflow_id: workflow_id.to_string(),
+                    property: Some(WorkflowProperty::Inputs(reference(rest, &mut pointer))),
+                }
+            }
+            ["workflows", workflow_id, "outputs", rest @ ..] if !rest.is_empty() => {
+                Expression::Workflows {
+                    workflow_id: workflow_id.to_string(),
+                    property: Some(WorkflowProperty::Outputs(reference(rest, &mut pointer))),
+                }
+            }
+            ["workflows", ..] => {
+                return Err(syntax("expected $workflows.<workflowId>.inputs|outputs.<name>"));
+            }
+            ["sourceDescriptions", name, rest @ ..] => Expression::SourceDescriptions {
+                name: name.to_string(),
+                reference: (!rest.is_empty()).then(|| rest.join(".")),
+            },
+            ["components", component_type, rest @ ..] if !rest.is_empty() => {
+                Expression::Components {
+                    component_type: component_type.to_string(),
+                    name: rest.join("."),
+                }
+            }
+            _ => return Err(syntax("unknown expression")),
+        };
+
+        if pointer.is_some() {
+            return Err(syntax("a JSON pointer is only allowed after a body or a named value"));
+        }
+
+        Ok(expression)
+    }
+}
+
+impl Expression {
+    /// Where the expression's value sits in a context document that mirrors the
+    /// expression paths: `$steps.a.outputs.b#/c` reads `/steps/a/outputs/b/c`.
+    /// Dots in input and output names descend into nested objects.
+    pub fn context_pointer(&self) -> String {
+        let (segments, pointer): (Vec<&str>, Option<&str>) = match self {
+            Expression::Url => (vec!["url"], None),
+            Expression::Method => (vec!["method"], None),
+            Expression::StatusCode => (vec!["statusCode"], None),
+            Expression::Request(source) => source_segments("request", source),
+            Expression::Response(source) => source_segments("response", source),
+            Expression::Inputs(reference) => reference_segments(vec!["inputs"], reference),
+            Expression::Outputs(reference) => reference_segments(vec!["outputs"], reference),
+            Expression::Steps { step_id, outputs } => match outputs {
+                Some(reference) => {
+                    reference_segments(vec!["steps", step_id, "outputs"], reference)
+                }
+                None => (vec!["steps", step_id], None),
+            },
+            Expression::Workflows { workflow_id, property } => match property {
+                Some(WorkflowProperty::Inputs(reference)) => {
+                    reference_segments(vec!["workflows", workflow_id, "inputs"], reference)
+                }
+                Some(WorkflowProperty::Outputs(reference)) => {
+                    reference_segments(vec!["workflows", workflow_id, "outputs"], reference)
+                }
+                None => (vec!["workflows", workflow_id], None),
+            },
+            Expression::SourceDescriptions { name, reference } => {
+                let mut segments = vec!["sourceDescriptions", name.as_str()];
+                segments.extend(reference.as_deref());
+                (segments, None)
+            }
+            Expression::Components { component_type, name } => {
+                (vec!["components", component_type, name], None)
+            }
+        };
+
+        let mut context_pointer = segments
+            .iter()
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect::<String>();
+        context_pointer.push_str(pointer.unwrap_or_default());
+
+        context_pointer
+    }
+}
+
+fn source_segments<'a>(message: &'a str, source: &'a Source) -> (Vec<&'a str>, Option<&'a str>) {
+    match source {
+        Source::Header(name) => (vec![message, "header", name], None),
+        Source::Query(name) => (vec![message, "query", name], None),
+        Source::Path(name) => (vec![message, "path", name], None),
+        Source::Body(pointer) => (vec![message, "body"], pointer.as_deref()),
+    }
+}
+
+fn reference_segments<'a>(
+    mut segments: Vec<&'a str>,
+    reference: &'a Reference,
+) -> (Vec<&'a str>, Option<&'a str>) {
+    segments.extend(reference.name.split('.'));
+
+    (segments, reference.pointer.as_deref())
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Url => write!(f, "$url"),
+            Expression::Method => write!(f, "$method"),
+            Expression::StatusCode => write!(f, "$statusCode"),
+            Expression::Request(source) => write!(f, "$request.{source}"),
+            Expression::Response(source) => write!(f, "$response.{source}"),
+            Expression::Inputs(reference) => write!(f, "$inputs.{reference}"),
+            Expression::Outputs(reference) => write!(f, "$outputs.{reference}"),
+            Expression::Steps { step_id, outputs } => match outputs {
+                Some(reference) => write!(f, "$steps.{step_id}.outputs.{reference}"),
+                None => write!(f, "$steps.{step_id}"),
+            },
+            Expression::Workflows { workflow_id, property } => match property {
+                Some(WorkflowProperty::Inputs(reference)) => {
+                    write!(f, "$workflows.{workflow_id}.inputs.{reference}")
+                }
+                Some(WorkflowProperty::Outputs(reference)) => {
+                    write!(f, "$workflows.{workflow_id}.outputs.{reference}")
+                }
+                None => write!(f, "$workflows.{workflow_id}"),
+            },
+            Expression::SourceDescriptions { name, reference } => match reference {
+                Some(reference) => write!(f, "$sourceDescriptions.{name}.{reference}"),
+                None => write!(f, "$sourceDescriptions.{name}"),
+            },
+            Expression::Components { component_type, name } => {
+                write!(f, "$components.{component_type}.{name}")
+            }
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Header(name) => write!(f, "header.{name}"),
+            Source::Query(name) => write!(f, "query.{name}"),
+            Source::Path(name) => write!(f, "path.{name}"),
+            Source::Body(Some(pointer)) => write!(f, "body#{pointer}"),
+            Source::Body(None) => write!(f, "body"),
+        }
+    }
+}
+
+impl Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pointer {
+            Some(pointer) => write!(f, "{}#{}", self.name, pointer),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+fn source(segments: &[&str], pointer: &mut Option<String>) -> Result<Source, &'static str> {
+    match segments {
+        ["header", rest @ ..] if !rest.is_empty() => Ok(Source::Header(rest.join("."))),
+        ["query", rest @ ..] if !rest.is_empty() => Ok(Source::Query(rest.join("."))),
+        ["path", rest @ ..] if !rest.is_empty() => Ok(Source::Path(rest.join("."))),
+        ["body"] => Ok(Source::Body(pointer.take())),
+        _ => Err("expected header.<name>, query.<name>, path.<name> or body"),
+    }
+}
+
+fn reference(segments: &[&str], pointer: &mut Option<String>) -> Reference {
+    Reference {
+        name: segments.join("."),
+        pointer: pointer.take(),
+    }
+}
+
+/// Checks a JSON pointer (RFC 6901): empty, or `/`-separated tokens where `~`
+/// only escapes `0` or `1`.
+fn validate_pointer(pointer: &str) -> Result<(), &'static str> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return Err("a JSON pointer must start with '/'");
+    }
+
+    let mut chars = pointer.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' && !matches!(chars.next(), Some('0' | '1')) {
+            return Err("'~' in a JSON pointer must be followed by '0' or '1'");
+        }
+    }
+
+    Ok(())
+}
+
+/// Splits a string into text and the `{$...}` expressions embedded in it.
+pub fn parse_template(text: &str) -> Result<Vec<TemplatePart>, ExpressionError> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{$") {
+        if start > 0 {
+            parts.push(TemplatePart::Text(rest[..start].to_string()));
+        }
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            return Err(ExpressionError::UnclosedTemplate(text.to_string()));
+        };
+        parts.push(TemplatePart::Expression(rest[start + 1..end].parse()?));
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Text(rest.to_string()));
+    }
+
+    Ok(parts)
+}
+
+/// Parses a value such as a parameter value or an output: either a whole
+/// runtime expression, or text with `{$...}` expressions embedded.
+pub fn parse_value(text: &str) -> Result<Vec<Expression>, ExpressionError> {
+    if text.starts_with('$') {
+        return Ok(vec![text.parse()?]);
+    }
+
+    let parts = parse_template(text)?;
+
+    Ok(parts
+        .into_iter()
+        .filter_map(|part| match part {
+            TemplatePart::Expression(expression) => Some(expression),
+            TemplatePart::Text(_) => None,
+        })
+        .collect())
+}
+
+/// Lists runtime expressions that do not parse, or that reference steps, inputs,
+/// outputs, workflows, source descriptions or reusable parameters the document
+/// does not declare.
+pub fn validate(arazzo: &ArazzoDocument) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for workflow in &arazzo.workflows {
+        for step in &workflow.steps {
+            for expression in step_expressions(arazzo, step) {
+                let problem = match expression {
+                    Ok(expression) => check_reference(arazzo, workflow, &expression),
+                    Err(error) => Some(error.to_string()),
+                };
+                if let Some(problem) = problem {
+                    problems.push(format!("{}.{}: {}", workflow.workflow_id, step.step_id, problem));
+                }
+            }
+            for problem in step_criteria(step).filter_map(check_criterion) {
+                problems.push(format!("{}.{}: {}", workflow.workflow_id, step.step_id, problem));
+            }
+        }
+
+        for value in workflow.outputs.iter().flat_map(|outputs| outputs.values()) {
+            let output_problems = match parse_value(value) {
+                Ok(expressions) => expressions
+                    .iter()
+                    .filter_map(|expression| check_reference(arazzo, workflow, expression))
+                    .collect(),
+                Err(error) => vec![error.to_string()],
+            };
+            for problem in output_problems {
+                problems.push(format!("{}: {}", workflow.workflow_id, problem));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Every runtime expression a step uses, in parameters, the request body,
+/// criteria contexts, simple conditions and outputs.
+fn step_expressions(
+    arazzo: &ArazzoDocument,
+    step: &Step,
+) -> Vec<Result<Expression, ExpressionError>> {
+    let mut expressions = Vec::new();
+    let mut push_value = |text: &str| match parse_value(text) {
+        Ok(parsed) => expressions.extend(parsed.into_iter().map(Ok)),
+        Err(error) => expressions.push(Err(error)),
+    };
+
+    for parameter in step.parameters.iter().flatten() {
+        if let StepParameter::Reusable(reusable) = parameter {
+            push_value(&reusable.reference);
+        }
+        if let Some(parameter) = arazzo.resolve_parameter(parameter) {
+            for_each_string(&parameter.value, &mut push_value);
+        }
+    }
+    if let Some(payload) = step.request_body.as_ref().and_then(|b| b.payload.as_ref()) {
+        for_each_string(payload, &mut push_value);
+    }
+    for value in step.outputs.iter().flat_map(|outputs| outputs.values()) {
+        push_value(value);
+    }
+
+    for criterion in step_criteria(step) {
+        if let Some(context) = criterion.context.as_deref() {
+            push_value(context);
+        }
+    }
+
+    for criterion in step_criteria(step) {
+        if let Some(condition) = criterion.condition.as_deref()
+            && criteria_type_name(criterion) == CriteriaTypeName::Simple
+            && let Ok(condition) = condition.parse::<Condition>()
+        {
+            expressions.extend(condition.expressions().into_iter().cloned().map(Ok));
+        }
+    }
+
+    expressions
+}
+
+/// The step's success criteria followed by the criteria of its actions.
+pub fn step_criteria(step: &Step) -> impl Iterator<Item = &Criteria> {
+    let actions = step.on_success.iter().chain(step.on_failure.iter()).flatten();
+
+    step.success_criteria
+        .iter()
+        .flatten()
+        .chain(actions.flat_map(|action| action.criteria.iter().flatten()))
+}
+
+pub fn criteria_type_name(criterion: &Criteria) -> CriteriaTypeName {
+    match &criterion.criteria_type {
+        Some(CriteriaType::Name(name)) => *name,
+        Some(CriteriaType::Expression(expression_type)) => expression_type.name,
+        None => CriteriaTypeName::Simple,
+    }
+}
+
+/// Checks the syntax of simple, regex and JSONPath conditions. XPath is not checked.
+fn check_criterion(criterion: &Criteria) -> Option<String> {
+    let condition = criterion.condition.as_deref()?;
+
+    match criteria_type_name(criterion) {
+        CriteriaTypeName::Regex => regex::Regex::new(condition).err().map(|error| {
+            let reason = error.to_string();
+            let reason = reason.lines().last().unwrap_or_default().trim_start_matches("error: ");
+            format!("invalid regex '{condition}': {reason}")
+        }),
+        CriteriaTypeName::JsonPath => serde_json_path::JsonPath::parse(condition)
+            .err()
+            .map(|error| format!("invalid JSONPath '{condition}': {error}")),
+        CriteriaTypeName::Simple => {
+            condition.parse::<Condition>().err().map(|error| error.to_string())
+        }
+        CriteriaTypeName::XPath => None,
+    }
+}
+
+fn for_each_string(value: &serde_json::Value, f: &mut impl FnMut(&str)) {
+    match value {
+        serde_json::Value::String(text) => f(text),
+        serde_json::Value::Array(values) => values.iter().for_each(|v| for_each_string(v, f)),
+        serde_json::Value::Object(values) => values.values().for_each(|v| for_each_string(v, f)),
+        _ => {}
+    }
+}
+
+fn check_reference(
+    arazzo: &ArazzoDocument,
+    workflow: &Workflow,
+    expression: &Expression,
+) -> Option<String> {
+    let declared = match expression {
+        Expression::Inputs(reference) => {
+            input_names(workflow).is_none_or(|names| declares(&names, reference))
+        }
+        Expression::Outputs(reference) => declares(&output_names(workflow), reference),
+        Expression::Steps { step_id, outputs } => {
+            let Some(step) = workflow.steps.iter().find(|s| &s.step_id == step_id) else {
+                return Some(format!("unknown step '{step_id}' in '{expression}'"));
+            };
+            outputs.as_ref().is_none_or(|reference| {
+                let names = step.outputs.iter().flat_map(|o| o.keys()).map(String::as_str);
+                declares(&names.collect::<Vec<&str>>(), reference)
+            })
+        }
+        Expression::Workflows { workflow_id, property } => {
+            let Some(other) = arazzo.workflows.iter().find(|w| &w.workflow_id == workflow_id)
+            else {
+                return Some(format!("unknown workflow '{workflow_id}' in '{expression}'"));
+            };
+            match property {
+                Some(WorkflowProperty::Inputs(reference)) => {
+                    input_names(other).is_none_or(|names| declares(&names, reference))
+                }
+                Some(WorkflowProperty::Outputs(reference)) => {
+                    declares(&output_names(other), reference)
+                }
+                None => true,
+            }
+        }
+        Expression::Components { component_type, name } if component_type == "parameters" => {
+            let parameters = arazzo.components.as_ref().and_then(|c| c.parameters.as_ref());
+            parameters.is_some_and(|parameters| parameters.contains_key(name))
+        }
+        Expression::SourceDescriptions { name, .. } => {
+            if !arazzo.source_descriptions.iter().flatten().any(|s| &s.name == name) {
+                return Some(format!("unknown source description '{name}' in '{expression}'"));
+            }
+            true
+        }
+        _ => true,
+    };
+
+    (!declared).then(|| format!("unknown reference '{expression}'"))
+}
+
+/// Names of the inputs declared in the workflow's JSON schema, or `None` when
+/// the schema does not list its properties.
+fn input_names(workflow: &Workflow) -> Option<Vec<&str>> {
+    workflow
+        .inputs
+        .as_ref()
+        .and_then(|inputs| inputs.get("properties"))
+        .and_then(|properties| properties.as_object())
+        .map(|properties| properties.keys().map(String::as_str).collect())
+}
+
+fn output_names(workflow: &Workflow) -> Vec<&str> {
+    workflow.outputs.iter().flat_map(|o| o.keys()).map(String::as_str).collect()
+}
+
+/// Whether `names` holds the reference's name, or its first dot-separated segment
+/// when the name reaches into a nested value.
+fn declares(names: &[&str], reference: &Reference) -> bool {
+    let root = reference.name.split('.').next().unwrap_or_default();
+
+    names.contains(&reference.name.as_str()) || names.contains(&root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::ReusableParameter;
+
+    #[test]
+    fn parse_expressions() {
+        let actual = [
+            "$statusCode",
+            "$request.header.X-Request-Id",
+            "$response.body#/items/0/id",
+            "$inputs.amount",
+            "$steps.createFoo.outputs.foo#/id",
+            "$workflows.login.outputs.token",
+            "$sourceDescriptions.fooApi.getFoo",
+            "$components.parameters.page",
+        ]
+        .map(|text| text.parse::<Expression>());
+
+        let expected = [
+            Ok(Expression::StatusCode),
+            Ok(Expression::Request(Source::Header(String::from("X-Request-Id")))),
+            Ok(Expression::Response(Source::Body(Some(String::from("/items/0/id"))))),
+            Ok(Expression::Inputs(Reference {
+                name: String::from("amount"),
+                pointer: None,
+            })),
+            Ok(Expression::Steps {
+                step_id: String::from("createFoo"),
+                outputs: Some(Reference {
+                    name: String::from("foo"),
+                    pointer: Some(String::from("/id")),
+                }),
+            }),
+            Ok(Expression::Workflows {
+                workflow_id: String::from("login"),
+                property: Some(WorkflowProperty::Outputs(Reference {
+                    name: String::from("token"),
+                    pointer: None,
+                })),
+            }),
+            Ok(Expression::SourceDescriptions {
+                name: String::from("fooApi"),
+                reference: Some(String::from("getFoo")),
+            }),
+            Ok(Expression::Components {
+                component_type: String::from("parameters"),
+                name: String::from("page"),
+            }),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_invalid_expressions() {
+        let actual = [
+            "$foo",
+            "$response.cookie.id",
+            "$steps.createFoo.inputs.id",
+            "$response.body#id",
+            "$response.body#/a~2",
+            "$statusCode#/code",
+            "$inputs.",
+        ]
+        .map(|text| text.parse::<Expression>().is_err());
+
+        assert_eq!([true; 7], actual);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let text = "$steps.createFoo.outputs.foo#/id";
+
+        let actual = text.parse::<Expression>().unwrap().to_string();
+
+        assert_eq!(text, actual);
+    }
+
+    #[test]
+    fn parse_embedded_template() {
+        let actual = parse_template("Bearer {$inputs.token}");
+
+        let expected = Ok(vec![
+            TemplatePart::Text(String::from("Bearer ")),
+            TemplatePart::Expression(Expression::Inputs(Reference {
+                name: String::from("token"),
+                pointer: None,
+            })),
+        ]);
+
+        assert_eq!(expected, actual);
+        assert!(parse_template("Bearer {$inputs.token").is_err());
+    }
+
+    #[test]
+    fn validate_fixture() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+
+        let actual = validate(&arazzo);
+
+        assert!(actual.is_empty(), "{:?}", actual);
+    }
+
+    #[test]
+    fn validate_criteria_syntax() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let mut arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        arazzo.workflows[0].steps[0].success_criteria = Some(vec![
+            Criteria {
+                context: Some(String::from("$response.body")),
+                condition: Some(String::from("^(ok")),
+                criteria_type: Some(CriteriaType::Name(CriteriaTypeName::Regex)),
+            },
+            Criteria {
+                context: Some(String::from("$response.body")),
+                condition: Some(String::from("$.items[?(@.id)]")),
+                criteria_type: Some(CriteriaType::Name(CriteriaTypeName::JsonPath)),
+            },
+            Criteria {
+                context: Some(String::from("$response.body")),
+                condition: Some(String::from("$.items[?(@.id")),
+                criteria_type: Some(CriteriaType::Name(CriteriaTypeName::JsonPath)),
+            },
+        ]);
+
+        let actual = validate(&arazzo);
+
+        let expected = vec![
+            String::from(
+                "authorizeThenCapture.createAuthorizationStep: invalid regex '^(ok': unclosed group",
+            ),
+            String::from(
+                "authorizeThenCapture.createAuthorizationStep: invalid JSONPath '$.items[?(@.id': at position 9, in long-hand segment, parser error",
+            ),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn validate_unknown_references() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let mut arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let workflow = &mut arazzo.workflows[0];
+        workflow.steps[1].outputs = Some(indexmap::IndexMap::from([
+            (String::from("a"), String::from("$steps.missingStep.outputs.id")),
+            (String::from("b"), String::from("$steps.createAuthorizationStep.outputs.missing")),
+            (String::from("c"), String::from("$inputs.missing")),
+            (String::from("d"), String::from("$response.cookie.id")),
+        ]));
+
+        let actual = validate(&arazzo);
+
+        let expected = vec![
+            String::from(
+                "authorizeThenCapture.captureAuthorizationStep: unknown step 'missingStep' in '$steps.missingStep.outputs.id'",
+            ),
+            String::from(
+                "authorizeThenCapture.captureAuthorizationStep: unknown reference '$steps.createAuthorizationStep.outputs.missing'",
+            ),
+            String::from(
+                "authorizeThenCapture.captureAuthorizationStep: unknown reference '$inputs.missing'",
+            ),
+            String::from(
+                "authorizeThenCapture.captureAuthorizationStep: invalid runtime expression '$response.cookie.id': expected header.<name>, query.<name>, path.<name> or body",
+            ),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn validate_reusable_parameters() {
+        let content = std::fs::read_to_string("fixtures/reusable-parameters.yml").unwrap();
+        let mut arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let parameters = arazzo.workflows[0].steps[0].parameters.as_mut().unwrap();
+        parameters.push(StepParameter::Reusable(ReusableParameter {
+            reference: String::from("$components.parameters.missing"),
+            value: None,
+        }));
+
+        let actual = validate(&arazzo);
+
+        let expected = vec![String::from(
+            "listPets.listPetsStep: unknown reference '$components.parameters.missing'",
+        )];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn validate_every_workflow_output_reference() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let mut arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        arazzo.workflows[0].outputs = Some(indexmap::IndexMap::from([(
+            String::from("summary"),
+            String::from("{$steps.missingStep.outputs.id} by {$inputs.missing}"),
+        )]));
+
+        let actual = validate(&arazzo);
+
+        let expected = vec![
+            String::from(
+                "authorizeThenCapture: unknown step 'missingStep' in '$steps.missingStep.outputs.id'",
+            ),
+            String::from("authorizeThenCapture: unknown reference '$inputs.missing'"),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,711 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use indexmap::IndexMap;
+use serde_json::{Map, Value, json};
+
+const SHAPES: [(&str, &str, Shape); 5] = [
+    ("((", "))", Shape::Circle),
+    ("([", "])", Shape::Stadium),
+    ("[[", "]]", Shape::Subroutine),
+    ("[", "]", Shape::Rectangle),
+    ("{", "}", Shape::Rhombus),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct FromMermaidError(String);
+
+impl Display for FromMermaidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to read Mermaid flowchart: {}", self.0)
+    }
+}
+
+impl Error for FromMermaidError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Plain,
+    Rectangle,
+    Subroutine,
+    Rhombus,
+    Stadium,
+    Circle,
+}
+
+/// A node as first declared with a shape; links may mention it by name only.
+#[derive(Debug)]
+struct Node {
+    shape: Shape,
+    label: Option<String>,
+}
+
+#[derive(Debug)]
+struct Link {
+    from: String,
+    to: String,
+    label: Option<String>,
+    dotted: bool,
+    /// Whether the target is drawn with its shape, as steps reached without an action are.
+    shaped: bool,
+}
+
+#[derive(Debug, Default)]
+struct Subgraph {
+    id: String,
+    label: Option<String>,
+    /// Node names in the order they first appear in the subgraph.
+    nodes: Vec<String>,
+    links: Vec<Link>,
+}
+
+#[derive(Debug, Default)]
+struct Flowchart {
+    title: Option<String>,
+    nodes: IndexMap<String, Node>,
+    subgraphs: Vec<Subgraph>,
+    /// Links outside subgraphs, such as the dotted links from a step to the workflow it calls.
+    links: Vec<Link>,
+}
+
+/// Reads a flowchart in the subset this tool renders and returns an Arazzo
+/// document skeleton: one workflow per subgraph, one step per rectangle, and
+/// the success criteria and actions given by the rhombi and their
+/// `true`/`false` links. Operations, inputs and outputs are left to fill in.
+pub fn convert(content: &str) -> Result<Value, FromMermaidError> {
+    let flowchart = parse_flowchart(content)?;
+    if flowchart.subgraphs.is_empty() {
+        return Err(FromMermaidError(String::from("no subgraph: draw each workflow as a subgraph")));
+    }
+
+    let workflows = flowchart
+        .subgraphs
+        .iter()
+        .map(|subgraph| flowchart.workflow(subgraph))
+        .collect::<Vec<Value>>();
+
+    Ok(json!({
+        "arazzo": "1.0.1",
+        "info": {
+            "title": flowchart.title.as_deref().unwrap_or("Workflows"),
+            "version": "1.0.0",
+        },
+        "workflows": workflows,
+    }))
+}
+
+fn parse_flowchart(content: &str) -> Result<Flowchart, FromMermaidError> {
+    let mut flowchart = Flowchart::default();
+    let mut front_matter = false;
+    let mut header = false;
+    let mut current: Option<Subgraph> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let fail = |message: &str| FromMermaidError(format!("line {}: {}", index + 1, message));
+
+        if index == 0 && line == "---" {
+            front_matter = true;
+            continue;
+        }
+        if front_matter {
+            if line == "---" {
+                front_matter = false;
+            } else if let Some(title) = line.strip_prefix("title:") {
+                flowchart.title = Some(title.trim().to_string());
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with("%%") {
+            continue;
+        }
+
+        let keyword = line.split_whitespace().next().unwrap_or_default();
+        if !header {
+            if !matches!(keyword, "flowchart" | "graph") {
+                return Err(fail("expected 'flowchart' or 'graph'"));
+            }
+            header = true;
+            continue;
+        }
+
+        match keyword {
+            "subgraph" => {
+                if current.is_some() {
+                    return Err(fail("nested subgraphs are not supported"));
+                }
+                let (id, _, label, _) = parse_node(&line["subgraph".len()..]).map_err(fail)?;
+                current = Some(Subgraph {
+                    id,
+                    label,
+                    ..Subgraph::default()
+                });
+            }
+            "end" => {
+                let subgraph = current.take().ok_or_else(|| fail("'end' outside a subgraph"))?;
+                flowchart.subgraphs.push(subgraph);
+            }
+            "classDef" | "class" | "click" | "style" | "linkStyle" | "direction" => {}
+            _ => {
+                let (from, link) = parse_statement(line).map_err(fail)?;
+                for (name, shape, label) in [Some(from), link.as_ref().map(|l| l.1.clone())]
+                    .into_iter()
+                    .flatten()
+                {
+                    flowchart.declare(current.as_mut(), name, shape, label);
+                }
+                if let Some((link, _)) = link {
+                    match current.as_mut() {
+                        Some(subgraph) => subgraph.links.push(link),
+                        None if link.dotted => flowchart.links.push(link),
+                        None => return Err(fail("links between steps belong in a subgraph")),
+                    }
+                }
+            }
+        }
+    }
+
+    if !header {
+        return Err(FromMermaidError(String::from("no 'flowchart' or 'graph' declaration")));
+    }
+    if current.is_some() {
+        return Err(FromMermaidError(String::from("subgraph without 'end'")));
+    }
+
+    Ok(flowchart)
+}
+
+/// A node name with the shape and label it is drawn with, if any.
+type Declaration = (String, Shape, Option<String>);
+
+type Statement = (Declaration, Option<(Link, Declaration)>);
+
+/// A node, optionally linked to a second one: `a --> b`, `a -->|label| b` or `a -.-> b`.
+fn parse_statement(line: &str) -> Result<Statement, &str> {
+    let (from, from_shape, from_label, rest) = parse_node(line)?;
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return Ok(((from, from_shape, from_label), None));
+    }
+
+    let (dotted, rest) = match (rest.strip_prefix("-->"), rest.strip_prefix("-.->")) {
+        (Some(rest), _) => (false, rest),
+        (_, Some(rest)) => (true, rest),
+        _ => return Err("expected '-->' or '-.->'"),
+    };
+    let (label, rest) = match rest.strip_prefix('|') {
+        Some(rest) => {
+            let (label, rest) = rest.split_once('|').ok_or("unclosed link label")?;
+            (Some(unquote(label)), rest)
+        }
+        None => (None, rest),
+    };
+    let (to, to_shape, to_label, rest) = parse_node(rest)?;
+    if !rest.trim().is_empty() {
+        return Err("chained links are not supported");
+    }
+
+    let link = Link {
+        from: from.clone(),
+        to: to.clone(),
+        label,
+        dotted,
+        shaped: to_shape != Shape::Plain,
+    };
+
+    Ok(((from, from_shape, from_label), Some((link, (to, to_shape, to_label)))))
+}
+
+/// Splits `name["label"]` into the name, its shape, its label and the rest of the line.
+fn parse_node(text: &str) -> Result<(String, Shape, Option<String>, &str), &'static str> {
+    let text = text.trim_start();
+    let end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, '[' | '{' | '(' | '|'))
+        .unwrap_or(text.len());
+    let arrows = ["-->", "-.->"].iter().filter_map(|arrow| text[..end].find(arrow));
+    let end = arrows.fold(end, usize::min);
+    let (name, rest) = text.split_at(end);
+    if name.is_empty() {
+        return Err("expected a node name");
+    }
+
+    let Some((opening, closing, shape)) = SHAPES.iter().find(|(o, _, _)| rest.starts_with(o)) else {
+        return Ok((name.to_string(), Shape::Plain, None, rest));
+    };
+    let inner = &rest[opening.len()..];
+    let (label, rest) = match inner.strip_prefix('"') {
+        Some(quoted) => {
+            let (label, rest) = quoted.split_once('"').ok_or("unclosed quote")?;
+            (label, rest.strip_prefix(closing).ok_or("unclosed node shape")?)
+        }
+        None => inner.split_once(closing).ok_or("unclosed node shape")?,
+    };
+
+    Ok((name.to_string(), *shape, Some(label.replace("#quot;", "\"")), rest))
+}
+
+fn unquote(text: &str) -> String {
+    let text = text.trim();
+    let text = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')).unwrap_or(text);
+
+    text.replace("#quot;", "\"")
+}
+
+impl Flowchart {
+    fn declare(
+        &mut self,
+        subgraph: Option<&mut Subgraph>,
+        name: String,
+        shape: Shape,
+        label: Option<String>,
+    ) {
+        if let Some(subgraph) = subgraph
+            && !subgraph.nodes.contains(&name)
+        {
+            subgraph.nodes.push(name.clone());
+        }
+        let node = self.nodes.entry(name).or_insert(Node {
+            shape: Shape::Plain,
+            label: None,
+        });
+        if node.shape == Shape::Plain {
+            node.shape = shape;
+            node.label = label;
+        }
+    }
+
+    fn shape(&self, name: &str) -> Shape {
+        self.nodes.get(name).map_or(Shape::Plain, |node| node.shape)
+    }
+
+    fn label(&self, name: &str) -> Option<&str> {
+        self.nodes.get(name).and_then(|node| node.label.as_deref())
+    }
+
+    fn is_retry(&self, name: &str) -> bool {
+        let label = self.label(name).unwrap_or_default();
+
+        self.shape(name) == Shape::Stadium && label.starts_with("Retry")
+    }
+
+    /// Subgraphs and the collapsed nodes drawn for workflows outside the chart.
+    fn is_workflow(&self, name: &str) -> bool {
+        self.subgraphs.iter().any(|subgraph| subgraph.id == name)
+            || (self.shape(name) == Shape::Stadium && !self.is_retry(name))
+    }
+
+    /// Rhombi, and the nodes left without a shape for decisions without criteria.
+    fn is_decision(&self, name: &str) -> bool {
+        self.shape(name) == Shape::Rhombus
+            || self.subgraphs.iter().flat_map(|subgraph| &subgraph.links).any(|link| {
+                link.from == name && matches!(verdict(link), Some("true" | "false"))
+            })
+    }
+
+    fn is_step(&self, name: &str) -> bool {
+        matches!(self.shape(name), Shape::Plain | Shape::Rectangle | Shape::Subroutine)
+            && !self.is_workflow(name)
+            && !self.is_decision(name)
+    }
+
+    /// The workflow id a workflow node stands for: collapsed nodes of other
+    /// documents are labelled `<source>.<workflowId>`.
+    fn workflow_id(&self, name: &str) -> String {
+        match self.label(name) {
+            Some(label) if self.shape(name) == Shape::Stadium && label.contains('.') => {
+                format!("$sourceDescriptions.{label}")
+            }
+            Some(label) if self.shape(name) == Shape::Stadium => label.to_string(),
+            _ => name.to_string(),
+        }
+    }
+
+    fn workflow(&self, subgraph: &Subgraph) -> Value {
+        let sources = subgraph.links.iter().filter(|l| !l.dotted).map(|l| &l.from);
+        let mut step_names: Vec<&str> = Vec::new();
+        for name in sources.chain(subgraph.nodes.iter()) {
+            if self.is_step(name) && !step_names.contains(&name.as_str()) {
+                step_names.push(name);
+            }
+        }
+
+        let context = WorkflowContext {
+            flowchart: self,
+            subgraph,
+            prefix: format!("{}_", subgraph.id),
+        };
+        let steps = step_names.iter().map(|name| context.step(name)).collect::<Vec<Value>>();
+
+        let mut workflow = Map::new();
+        workflow.insert(String::from("workflowId"), json!(subgraph.id));
+        if let Some(label) = &subgraph.label {
+            workflow.insert(String::from("description"), json!(label));
+        }
+        workflow.insert(String::from("steps"), Value::Array(steps));
+
+        Value::Object(workflow)
+    }
+}
+
+struct WorkflowContext<'a> {
+    flowchart: &'a Flowchart,
+    subgraph: &'a Subgraph,
+    /// Node names are `<workflowId>_<stepId>` when rendered by this tool.
+    prefix: String,
+}
+
+impl WorkflowContext<'_> {
+    fn local_name<'a>(&self, name: &'a str) -> &'a str {
+        name.strip_prefix(self.prefix.as_str()).unwrap_or(name)
+    }
+
+    fn next(&self, from: &str) -> Option<&Link> {
+        self.subgraph.links.iter().find(|link| link.from == from && !link.dotted)
+    }
+
+    /// The link leaving a decision on `true` or `false`.
+    fn branch(&self, from: &str, verdict: &str) -> Option<&Link> {
+        let links = self.subgraph.links.iter();
+
+        links.filter(|link| link.from == from).find(|link| self::verdict(link) == Some(verdict))
+    }
+
+    fn step(&self, name: &str) -> Value {
+        let flowchart = self.flowchart;
+        let mut step = Map::new();
+        step.insert(String::from("stepId"), json!(self.local_name(name)));
+
+        let call = flowchart.links.iter().find(|link| link.from == name && link.dotted);
+        let label = flowchart.label(name);
+        match call {
+            Some(call) if flowchart.shape(name) == Shape::Subroutine => {
+                let workflow_id = flowchart.workflow_id(&call.to);
+                let default_label = workflow_id.strip_prefix("$sourceDescriptions.");
+                if let Some(label) = label
+                    && Some(label) != default_label
+                    && label != workflow_id
+                {
+                    step.insert(String::from("description"), json!(label));
+                }
+                step.insert(String::from("workflowId"), json!(workflow_id));
+                if let Some(parameters) = &call.label {
+                    step.insert(String::from("parameters"), parse_parameters(parameters));
+                }
+            }
+            _ => {
+                if let Some(label) = label {
+                    step.insert(String::from("description"), json!(label));
+                }
+            }
+        }
+
+        if let Some(decision) = self.next(name)
+            && flowchart.is_decision(&decision.to)
+        {
+            let decision = decision.to.as_str();
+            if let Some(label) = flowchart.label(decision).filter(|l| !l.is_empty()) {
+                step.insert(String::from("successCriteria"), parse_criteria(label));
+            }
+            // A step without `onSuccess` continues to the next step, drawn with its label.
+            let implicit = self.branch(decision, "true").is_some_and(|link| {
+                link.shaped && flowchart.is_step(&link.to) && link.to != name
+            });
+            for (key, verdict) in [("onSuccess", "true"), ("onFailure", "false")] {
+                if key == "onSuccess" && implicit {
+                    continue;
+                }
+                let actions = self.actions(name, decision, verdict);
+                if !actions.is_empty() {
+                    step.insert(String::from(key), Value::Array(actions));
+                }
+            }
+        }
+
+        Value::Object(step)
+    }
+
+    /// Follows a branch of a step's decision. Each rhombus met on the way is an
+    /// action with criteria, whose `false` link leads to the next action.
+    fn actions(&self, step: &str, decision: &str, verdict: &str) -> Vec<Value> {
+        let mut actions = Vec::new();
+        let mut from = decision;
+        let mut verdict = verdict;
+
+        while let Some(link) = self.branch(from, verdict) {
+            let target = link.to.as_str();
+            if !self.flowchart.is_decision(target) {
+                actions.push(self.action(step, None, target));
+                break;
+            }
+
+            let name = self.local_name(target);
+            let name = name.strip_suffix("Node").unwrap_or(name);
+            let mut action = match self.branch(target, "true") {
+                Some(link) => self.action(step, Some(name), &link.to),
+                None => self.action(step, Some(name), ""),
+            };
+            if let Some(label) = self.flowchart.label(target).filter(|l| !l.is_empty())
+                && let Value::Object(action) = &mut action
+            {
+                action.insert(String::from("criteria"), parse_criteria(label));
+            }
+            actions.push(action);
+
+            match self.branch(target, "false") {
+                Some(link) if self.flowchart.shape(&link.to) == Shape::Circle => break,
+                _ => {
+                    from = target;
+                    verdict = "false";
+                }
+            }
+        }
+
+        actions
+    }
+
+    /// The action reaching `target`: an end, a retry, or a goto to a step or
+    /// workflow. Actions drawn without a rhombus have no name in the chart, so
+    /// one is made up from their target.
+    fn action(&self, step: &str, name: Option<&str>, target: &str) -> Value {
+        let flowchart = self.flowchart;
+        let mut action = Map::new();
+        let mut insert = |key: &str, value: Value| action.insert(key.to_string(), value);
+
+        if flowchart.is_retry(target) {
+            let retry_name = target.strip_prefix(&format!("{step}_")).unwrap_or(target);
+            let retry_name = retry_name.strip_suffix("RetryNode").unwrap_or(retry_name);
+            insert("name", json!(name.unwrap_or(retry_name)));
+            insert("type", json!("retry"));
+            if let Some(back) = self.next(target).filter(|link| link.to != step) {
+                if flowchart.is_workflow(&back.to) {
+                    insert("workflowId", json!(flowchart.workflow_id(&back.to)));
+                } else {
+                    insert("stepId", json!(self.local_name(&back.to)));
+                }
+            }
+            let label = flowchart.label(target).unwrap_or_default();
+            let after = between(label, "after ", "s").and_then(|v| v.parse::<f64>().ok());
+            if let Some(after) = after {
+                insert("retryAfter", json!(after));
+            }
+            if let Some(limit) = between(label, "up to ", " times").and_then(|v| v.parse().ok()) {
+                insert("retryLimit", Value::Number(limit));
+            }
+        } else if flowchart.is_workflow(target) {
+            let workflow_id = flowchart.workflow_id(target);
+            let local_id = workflow_id.rsplit('.').next().unwrap_or_default();
+            insert("name", json!(name.map_or_else(|| goto_name(local_id), String::from)));
+            insert("type", json!("goto"));
+            insert("workflowId", json!(workflow_id));
+        } else if flowchart.is_step(target) && !target.is_empty() {
+            let step_id = self.local_name(target);
+            insert("name", json!(name.map_or_else(|| goto_name(step_id), String::from)));
+            insert("type", json!("goto"));
+            insert("stepId", json!(step_id));
+        } else {
+            insert("name", json!(name.unwrap_or("end")));
+            insert("type", json!("end"));
+        }
+
+        Value::Object(action)
+    }
+}
+
+/// The `true` or `false` of a link leaving a decision, ignoring coverage counts.
+fn verdict(link: &Link) -> Option<&str> {
+    link.label.as_deref().and_then(|label| label.split_whitespace().next())
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(start)?;
+    let (value, _) = rest.split_once(end)?;
+
+    Some(value)
+}
+
+/// `gotoConfirmStep` for a goto to `confirmStep`.
+fn goto_name(target: &str) -> String {
+    let mut chars = target.chars();
+    match chars.next() {
+        Some(first) => format!("goto{}", first.to_uppercase().chain(chars).collect::<String>()),
+        None => String::from("goto"),
+    }
+}
+
+/// `name = value` pairs separated by `<br>`, as drawn on the link to a called workflow.
+fn parse_parameters(label: &str) -> Value {
+    label
+        .split("<br>")
+        .filter_map(|parameter| parameter.split_once(" = "))
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+/// Undoes the rhombus labels: conditions joined with `&&`, regular
+/// expressions as `context ~ /pattern/` and queries as `jsonpath: query in context`.
+fn parse_criteria(label: &str) -> Value {
+    label.split(" && ").map(parse_criterion).collect()
+}
+
+fn parse_criterion(text: &str) -> Value {
+    if let Some(pattern) = text.strip_prefix('/').and_then(|t| t.strip_suffix('/')) {
+        return json!({ "condition": pattern, "type": "regex" });
+    }
+    if let Some((context, pattern)) = text.split_once(" ~ /")
+        && let Some(pattern) = pattern.strip_suffix('/')
+    {
+        return json!({ "context": context, "condition": pattern, "type": "regex" });
+    }
+    if let Some((kind, query)) = text.split_once(": ") {
+        let (kind, version) = kind.split_once(' ').map_or((kind, None), |(k, v)| (k, Some(v)));
+        if matches!(kind, "jsonpath" | "xpath") {
+            let criteria_type = match version {
+                Some(version) => json!({ "type": kind, "version": version }),
+                None => json!(kind),
+            };
+            return match query.rsplit_once(" in ") {
+                Some((query, context)) => {
+                    json!({ "context": context, "condition": query, "type": criteria_type })
+                }
+                None => json!({ "condition": query, "type": criteria_type }),
+            };
+        }
+    }
+
+    json!({ "condition": text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{MermaidFlowchart, Renderer};
+
+    fn round_trip(content: &str) -> (String, String) {
+        let arazzo = crate::parse(content).unwrap();
+        let flowchart = MermaidFlowchart::builder().build().render(&arazzo);
+
+        let skeleton = yaml_serde::to_string(&convert(&flowchart).unwrap()).unwrap();
+        println!("{}", skeleton);
+        let converted = crate::parse(&skeleton).unwrap();
+
+        (flowchart, MermaidFlowchart::builder().build().render(&converted))
+    }
+
+    #[test]
+    fn round_trip_fixture() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+
+        let (expected, actual) = round_trip(&content);
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn round_trip_actions() {
+        let content = concat!(
+            "arazzo: 1.0.1\n",
+            "info:\n",
+            "  title: Orders\n",
+            "  version: 1.0.0\n",
+            "sourceDescriptions:\n",
+            "  - name: shipping\n",
+            "    url: ./shipping.arazzo.yml\n",
+            "    type: arazzo\n",
+            "workflows:\n",
+            "  - workflowId: placeOrder\n",
+            "    steps:\n",
+            "      - stepId: createOrder\n",
+            "        description: Create the order.\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 201\n",
+            "          - context: $response.body\n",
+            "            condition: $.items[?(@.stock == 0)]\n",
+            "            type: jsonpath\n",
+            "        onFailure:\n",
+            "          - name: outOfStock\n",
+            "            type: goto\n",
+            "            stepId: notify\n",
+            "            criteria:\n",
+            "              - context: $response.header.X-Reason\n",
+            "                condition: ^stock$\n",
+            "                type: regex\n",
+            "          - name: retryCreate\n",
+            "            type: retry\n",
+            "            retryAfter: 1.5\n",
+            "            retryLimit: 3\n",
+            "      - stepId: ship\n",
+            "        workflowId: $sourceDescriptions.shipping.shipOrder\n",
+            "        parameters:\n",
+            "          - name: orderId\n",
+            "            value: $steps.createOrder.outputs.id\n",
+            "      - stepId: notify\n",
+            "        onSuccess:\n",
+            "          - name: audit\n",
+            "            type: goto\n",
+            "            workflowId: audit\n",
+            "  - workflowId: audit\n",
+            "    description: Record what happened.\n",
+            "    steps:\n",
+            "      - stepId: record\n",
+        );
+
+        let (expected, actual) = round_trip(content);
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn convert_sketch() {
+        let content = concat!(
+            "flowchart LR\n",
+            "    subgraph checkout[\"Pay for the cart.\"]\n",
+            "    pay[\"Pay\"] --> payNode{$statusCode == 200}\n",
+            "    payNode -->|true| confirm\n",
+            "    payNode -->|false| checkoutEndNode((End))\n",
+            "    confirm[\"Confirm\"] --> checkoutEndNode((End))\n",
+            "    end\n",
+        );
+
+        let actual = yaml_serde::to_string(&convert(content).unwrap()).unwrap();
+
+        let expected = concat!(
+            "arazzo: 1.0.1\n",
+            "info:\n",
+            "  title: Workflows\n",
+            "  version: 1.0.0\n",
+            "workflows:\n",
+            "- workflowId: checkout\n",
+            "  description: Pay for the cart.\n",
+            "  steps:\n",
+            "  - stepId: pay\n",
+            "    description: Pay\n",
+            "    successCriteria:\n",
+            "    - condition: $statusCode == 200\n",
+            "    onSuccess:\n",
+            "    - name: gotoConfirm\n",
+            "      type: goto\n",
+            "      stepId: confirm\n",
+            "    onFailure:\n",
+            "    - name: end\n",
+            "      type: end\n",
+            "  - stepId: confirm\n",
+            "    description: Confirm\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn convert_without_subgraph() {
+        let actual = convert("flowchart TD\n    a --> b\n");
+
+        let expected = Err(FromMermaidError(String::from(
+            "line 2: links between steps belong in a subgraph",
+        )));
+
+        assert_eq!(expected, actual);
+    }
+}
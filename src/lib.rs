@@ -0,0 +1,163 @@
+//! Convert Arazzo workflows into Mermaid diagrams.
+//!
+//! ```
+//! use arazzo2mermaid::renderer::MermaidFlowchart;
+//! use arazzo2mermaid::{Direction, Renderer};
+//!
+//! let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+//! let arazzo = arazzo2mermaid::parse(&content).unwrap();
+//!
+//! let flowchart = MermaidFlowchart::builder()
+//!     .direction(Direction::LR)
+//!     .workflows(["authorizeThenCapture"], 0)
+//!     .styled()
+//!     .build();
+//!
+//! assert!(flowchart.render(&arazzo).contains("flowchart LR"));
+//! ```
+
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use crate::renderer::MermaidFlowchart;
+
+pub mod arazzo;
+pub mod composition;
+pub mod condition;
+pub mod expression;
+#[cfg(feature = "cli")]
+pub mod from_mermaid;
+#[cfg(feature = "cli")]
+pub mod lsp;
+#[cfg(feature = "cli")]
+pub mod mdbook;
+pub mod openapi;
+pub mod operation;
+#[cfg(feature = "cli")]
+pub mod paths;
+pub mod renderer;
+#[cfg(all(feature = "cli", feature = "http"))]
+pub mod runner;
+#[cfg(feature = "cli")]
+pub mod simulate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use crate::arazzo::ArazzoDocument;
+pub use crate::composition::CompositionError;
+pub use crate::openapi::OpenApiError;
+pub use crate::renderer::coverage::CoverageError;
+pub use crate::renderer::{ClassDefs, Direction, FlowchartOptions, Renderer, StepLabel};
+#[cfg(feature = "cli")]
+pub use crate::simulate::SimulationError;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ParseError {
+    Yaml(yaml_serde::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Yaml(error) => write!(f, "Failed to parse YAML: {}", error),
+            ParseError::Json(error) => write!(f, "Failed to parse JSON: {}", error),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Yaml(error) => Some(error),
+            ParseError::Json(error) => Some(error),
+        }
+    }
+}
+
+/// Parses an Arazzo document written in YAML.
+pub fn parse(content: &str) -> Result<ArazzoDocument, ParseError> {
+    yaml_serde::from_str(content).map_err(ParseError::Yaml)
+}
+
+/// Parses an Arazzo document written in JSON.
+pub fn parse_json(content: &str) -> Result<ArazzoDocument, ParseError> {
+    serde_json::from_str(content).map_err(ParseError::Json)
+}
+
+/// Renders every workflow of the document as a Mermaid flowchart with the default options.
+pub fn render(arazzo: &ArazzoDocument) -> String {
+    MermaidFlowchart::builder().build().render(arazzo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_with_builder() {
+        let arazzo = parse(concat!(
+            "arazzo: 1.0.1\n",
+            "info:\n",
+            "  title: Workflows\n",
+            "  version: 1.0.0\n",
+            "workflows:\n",
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 200\n",
+            "        onFailure:\n",
+            "          - name: retryFoo\n",
+            "            type: retry\n",
+            "            retryAfter: 1.5\n",
+            "            retryLimit: 3\n",
+        ))
+        .unwrap();
+        let sut = MermaidFlowchart::builder()
+            .direction(Direction::LR)
+            .theme("forest")
+            .theme_variable("primaryColor", "#ff6600")
+            .class_defs(ClassDefs {
+                step: String::from("fill:#fff"),
+                ..ClassDefs::default()
+            })
+            .build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "%%{init: {\"theme\":\"forest\",\"themeVariables\":{\"primaryColor\":\"#ff6600\"}}}%%\n",
+            "flowchart LR\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_stepFoo_retryFooRetryNode([\"Retry after 1.5s up to 3 times\"])\n",
+            "    workflowFoo_stepFoo_retryFooRetryNode([\"Retry after 1.5s up to 3 times\"]) --> workflowFoo_stepFoo\n",
+            "    end\n",
+            "    classDef stepNode fill:#fff\n",
+            "    classDef decisionNode fill:#fef7e0,stroke:#f9ab00\n",
+            "    classDef endNode fill:#e6f4ea,stroke:#1e8e3e\n",
+            "    classDef crossWorkflowNode fill:#f3e8fd,stroke:#9334e6,stroke-dasharray:4 2\n",
+            "    classDef retryNode fill:#fce8e6,stroke:#d93025\n",
+            "    class workflowFoo_stepFoo stepNode\n",
+            "    class workflowFoo_stepFooNode decisionNode\n",
+            "    class workflowFooEndNode endNode\n",
+            "    class workflowFoo_stepFoo_retryFooRetryNode retryNode\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_invalid_json() {
+        let actual = parse_json("{\"arazzo\": \"1.0.1\"}").unwrap_err().to_string();
+
+        assert!(actual.starts_with("Failed to parse JSON: missing field"), "{actual}");
+    }
+}
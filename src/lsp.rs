@@ -0,0 +1,756 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use serde_json::{Value, json};
+
+use crate::arazzo::{ArazzoDocument, Workflow};
+use crate::renderer::{Direction, MermaidFlowchart, Renderer};
+use crate::{ParseError, expression};
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const PARSE_ERROR: i64 = -32700;
+
+const STEPS_PREFIX: &str = "$steps.";
+
+/// Serves the Language Server Protocol over `input` and `output`, typically
+/// stdin and stdout, until the client sends `exit` or closes the stream.
+pub fn run(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut server = Server::default();
+
+    while let Some(content) = read_message(&mut input)? {
+        let replies = match serde_json::from_str(&content) {
+            Ok(message) => server.handle(&message),
+            Err(error) => vec![response(Value::Null, Err(RequestError::new(PARSE_ERROR, error)))],
+        };
+        for reply in replies {
+            write_message(&mut output, &reply)?;
+        }
+        if server.exited {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the content of the next `Content-Length` framed message.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() && content_length.is_some() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let mut content = vec![0; content_length.unwrap_or_default()];
+    input.read_exact(&mut content)?;
+    String::from_utf8(content).map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+    output.flush()
+}
+
+#[derive(Debug, PartialEq)]
+struct RequestError {
+    code: i64,
+    message: String,
+}
+
+impl RequestError {
+    fn new(code: i64, message: impl ToString) -> RequestError {
+        RequestError {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// An open document: its latest text and the last version of it that parsed,
+/// so completion and the preview keep working while the user types.
+struct Document {
+    text: String,
+    arazzo: Option<ArazzoDocument>,
+}
+
+/// The state of the language server, one JSON-RPC message at a time.
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+    exited: bool,
+}
+
+impl Server {
+    /// Handles a request or notification and returns the messages to send
+    /// back: the response to a request and any diagnostics it published.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            return Vec::new();
+        };
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        let result = match method {
+            "initialize" => Ok(capabilities()),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "exit" => {
+                self.exited = true;
+                return Vec::new();
+            }
+            _ if self.shutdown => {
+                Err(RequestError::new(INVALID_REQUEST, "the server is shutting down"))
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                return self.update(uri, text);
+            }
+            "textDocument/didChange" => {
+                // Full synchronization: the last change holds the whole text.
+                let changes = params["contentChanges"].as_array().into_iter().flatten();
+                let mut texts = changes.filter_map(|change| change["text"].as_str());
+                return match texts.next_back() {
+                    Some(text) => self.update(uri, text),
+                    None => Vec::new(),
+                };
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return vec![publish_diagnostics(uri, Vec::new())];
+            }
+            "textDocument/definition" => self.definition(params),
+            "textDocument/completion" => self.completion(params),
+            "arazzo/render" => self.render(params),
+            _ => Err(RequestError::new(METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        };
+
+        match message.get("id") {
+            Some(id) => vec![response(id.clone(), result)],
+            None => Vec::new(),
+        }
+    }
+
+    fn update(&mut self, uri: &str, text: &str) -> Vec<Value> {
+        let parsed = parse_document(text);
+        let diagnostics = match &parsed {
+            Ok(arazzo) => validate(text, arazzo),
+            Err(error) => vec![parse_diagnostic(text, error)],
+        };
+
+        let document = self.documents.entry(uri.to_string()).or_insert(Document {
+            text: String::new(),
+            arazzo: None,
+        });
+        document.text = text.to_string();
+        if let Ok(arazzo) = parsed {
+            document.arazzo = Some(arazzo);
+        }
+
+        vec![publish_diagnostics(uri, diagnostics)]
+    }
+
+    fn document<'a>(&'a self, params: &'a Value) -> Result<(&'a str, &'a Document), RequestError> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .ok_or_else(|| RequestError::new(INVALID_PARAMS, "missing textDocument.uri"))?;
+        let document = self
+            .documents
+            .get(uri)
+            .ok_or_else(|| RequestError::new(INVALID_PARAMS, format!("unknown document: {uri}")))?;
+
+        Ok((uri, document))
+    }
+
+    /// Jumps from a `stepId` or `workflowId` in an action or step, or from a
+    /// `$steps.<id>` expression, to the step or workflow it names.
+    fn definition(&self, params: &Value) -> Result<Value, RequestError> {
+        let (uri, document) = self.document(params)?;
+        let (line, character) = position(params);
+        let Some(content) = document.text.lines().nth(line) else {
+            return Ok(Value::Null);
+        };
+        let offset = byte_offset(content, character);
+        let entries = outline(&document.text);
+
+        let reference = entries
+            .iter()
+            .find(|entry| {
+                entry.line == line
+                    && entry.is_reference()
+                    && (entry.start..=entry.start + entry.value.len()).contains(&offset)
+            })
+            .map(|entry| (entry.key, entry.value, entry.workflow))
+            .or_else(|| {
+                let step_id = step_reference_at(content, offset)?;
+                Some(("stepId", step_id, workflow_at(&entries, line)))
+            });
+        let Some((key, id, workflow)) = reference else {
+            return Ok(Value::Null);
+        };
+
+        let definition = entries.iter().find(|entry| {
+            entry.is_definition()
+                && entry.key == key
+                && entry.value == id
+                && (key == "workflowId" || entry.workflow == workflow)
+        });
+
+        Ok(definition.map_or(Value::Null, |entry| json!({ "uri": uri, "range": entry.range() })))
+    }
+
+    /// Completes `$steps.<id>.outputs.<name>` one segment at a time.
+    fn completion(&self, params: &Value) -> Result<Value, RequestError> {
+        let (_, document) = self.document(params)?;
+        let (line, character) = position(params);
+        let Some(content) = document.text.lines().nth(line) else {
+            return Ok(json!([]));
+        };
+        let before = &content[..byte_offset(content, character)];
+        let Some(start) = before.rfind(STEPS_PREFIX) else {
+            return Ok(json!([]));
+        };
+        let path = &before[start + STEPS_PREFIX.len()..];
+        if !path.chars().all(|c| is_id_char(c) || c == '.') {
+            return Ok(json!([]));
+        }
+
+        let entries = outline(&document.text);
+        let workflow_id = workflow_at(&entries, line);
+        let workflow = document.arazzo.as_ref().and_then(|arazzo| {
+            arazzo.workflows.iter().find(|w| Some(w.workflow_id.as_str()) == workflow_id)
+        });
+
+        let items = match path.split('.').collect::<Vec<&str>>().as_slice() {
+            [_] => entries
+                .iter()
+                .filter(|entry| entry.is_step_definition() && entry.workflow == workflow_id)
+                .map(|entry| completion_item(entry.value, COMPLETION_REFERENCE))
+                .collect(),
+            [_, _] => vec![completion_item("outputs", COMPLETION_PROPERTY)],
+            [step_id, "outputs", _] => workflow
+                .and_then(|workflow| find_step_outputs(workflow, step_id))
+                .into_iter()
+                .flatten()
+                .map(|name| completion_item(name, COMPLETION_FIELD))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(Value::Array(items))
+    }
+
+    /// The `arazzo/render` request: the Mermaid flowchart of the last version
+    /// of the document that parsed, for a preview pane. Takes the optional
+    /// `workflowIds` and `direction` next to `textDocument`.
+    fn render(&self, params: &Value) -> Result<Value, RequestError> {
+        let (uri, document) = self.document(params)?;
+        let arazzo = document.arazzo.as_ref().ok_or_else(|| {
+            RequestError::new(INVALID_PARAMS, format!("document does not parse: {uri}"))
+        })?;
+
+        let mut builder = MermaidFlowchart::builder();
+        if let Some(direction) = params["direction"].as_str() {
+            let direction = direction
+                .parse::<Direction>()
+                .map_err(|error| RequestError::new(INVALID_PARAMS, error))?;
+            builder = builder.direction(direction);
+        }
+        if let Some(workflow_ids) = params["workflowIds"].as_array()
+            && !workflow_ids.is_empty()
+        {
+            builder = builder.workflows(workflow_ids.iter().filter_map(Value::as_str), 0);
+        }
+
+        Ok(json!({ "mermaid": builder.build().render(arazzo) }))
+    }
+}
+
+const COMPLETION_FIELD: u8 = 5;
+const COMPLETION_PROPERTY: u8 = 10;
+const COMPLETION_REFERENCE: u8 = 18;
+
+fn completion_item(label: &str, kind: u8) -> Value {
+    json!({ "label": label, "kind": kind })
+}
+
+fn find_step_outputs<'a>(workflow: &'a Workflow, step_id: &str) -> Option<Vec<&'a str>> {
+    let step = workflow.steps.iter().find(|step| step.step_id == step_id)?;
+    let outputs = step.outputs.as_ref()?;
+
+    Some(outputs.keys().map(String::as_str).collect())
+}
+
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": 1,
+            "definitionProvider": true,
+            "completionProvider": { "triggerCharacters": ["."] },
+        },
+        "serverInfo": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+fn response(id: Value, result: Result<Value, RequestError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn diagnostic(range: Value, message: impl ToString) -> Value {
+    json!({
+        "range": range,
+        "severity": 1,
+        "source": env!("CARGO_PKG_NAME"),
+        "message": message.to_string(),
+    })
+}
+
+/// JSON documents start with `{`; everything else is read as YAML.
+fn parse_document(text: &str) -> Result<ArazzoDocument, ParseError> {
+    if text.trim_start().starts_with('{') {
+        crate::parse_json(text)
+    } else {
+        crate::parse(text)
+    }
+}
+
+fn parse_diagnostic(text: &str, error: &ParseError) -> Value {
+    let (line, column) = match error {
+        ParseError::Yaml(error) => error.location().map_or((1, 1), |l| (l.line(), l.column())),
+        ParseError::Json(error) => (error.line(), error.column()),
+    };
+    let line = line.saturating_sub(1);
+    let content = text.lines().nth(line).unwrap_or_default();
+    let start = content.chars().take(column.saturating_sub(1)).map(char::len_utf8).sum();
+
+    diagnostic(line_range(line, content, start, content.len()), error)
+}
+
+/// Positions the problems of [`expression::validate`] on the step or workflow
+/// they belong to, and reports actions pointing at steps or workflows that do
+/// not exist on the `stepId` or `workflowId` naming them.
+fn validate(text: &str, arazzo: &ArazzoDocument) -> Vec<Value> {
+    let entries = outline(text);
+    let find_definition = |key: &str, id: &str, workflow_id: &str| {
+        entries.iter().find(|entry| {
+            entry.is_definition()
+                && entry.key == key
+                && entry.value == id
+                && entry.workflow == Some(workflow_id)
+        })
+    };
+    let document_start = || line_range(0, "", 0, 0);
+
+    let mut diagnostics = Vec::new();
+    for problem in expression::validate(arazzo) {
+        let located = arazzo.workflows.iter().find_map(|workflow| {
+            let workflow_id = workflow.workflow_id.as_str();
+            let step = workflow.steps.iter().find_map(|step| {
+                let message = problem.strip_prefix(&format!("{workflow_id}.{}: ", step.step_id))?;
+                Some((find_definition("stepId", &step.step_id, workflow_id), message))
+            });
+            step.or_else(|| {
+                let message = problem.strip_prefix(&format!("{workflow_id}: "))?;
+                Some((find_definition("workflowId", workflow_id, workflow_id), message))
+            })
+        });
+        let (entry, message) = located.unwrap_or((None, &problem));
+        diagnostics.push(diagnostic(entry.map_or_else(document_start, Entry::range), message));
+    }
+
+    for workflow in &arazzo.workflows {
+        let workflow_id = workflow.workflow_id.as_str();
+        let actions = workflow.steps.iter().flat_map(|step| {
+            step.on_success.iter().chain(step.on_failure.iter()).flatten()
+        });
+        for action in actions {
+            let (key, target) = match (&action.step_id, &action.workflow_id) {
+                (Some(step_id), _) if !workflow.steps.iter().any(|s| &s.step_id == step_id) => {
+                    ("stepId", step_id)
+                }
+                (_, Some(target_id))
+                    if !target_id.starts_with("$sourceDescriptions.")
+                        && !arazzo.workflows.iter().any(|w| &w.workflow_id == target_id) =>
+                {
+                    ("workflowId", target_id)
+                }
+                _ => continue,
+            };
+            let entry = entries.iter().find(|entry| {
+                entry.is_reference()
+                    && entry.key == key
+                    && entry.value == target
+                    && entry.workflow == Some(workflow_id)
+            });
+            let kind = if key == "stepId" { "step" } else { "workflow" };
+            let message = format!("unknown {kind} '{target}' in action '{}'", action.name);
+            diagnostics.push(diagnostic(entry.map_or_else(document_start, Entry::range), message));
+        }
+    }
+
+    diagnostics
+}
+
+/// A `key: value` line of a YAML document, with the key of the block it sits
+/// in and the workflow it belongs to. A line-based outline rather than a parse,
+/// so it still works on documents that are only half typed.
+#[derive(Debug, PartialEq)]
+struct Entry<'a> {
+    line: usize,
+    content: &'a str,
+    start: usize,
+    key: &'a str,
+    value: &'a str,
+    parent: Option<&'a str>,
+    workflow: Option<&'a str>,
+}
+
+impl Entry<'_> {
+    fn is_step_definition(&self) -> bool {
+        self.key == "stepId" && self.parent == Some("steps")
+    }
+
+    fn is_definition(&self) -> bool {
+        self.is_step_definition() || (self.key == "workflowId" && self.parent == Some("workflows"))
+    }
+
+    /// A `stepId` or `workflowId` in an action, or a step calling a workflow.
+    fn is_reference(&self) -> bool {
+        matches!(self.key, "stepId" | "workflowId") && !self.is_definition()
+    }
+
+    fn range(&self) -> Value {
+        line_range(self.line, self.content, self.start, self.start + self.value.len())
+    }
+}
+
+fn outline(text: &str) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    let mut blocks: Vec<(usize, &str)> = Vec::new();
+    let mut workflow = None;
+
+    for (line, content) in text.lines().enumerate() {
+        let indent = content.len() - content.trim_start_matches(' ').len();
+        let mut rest = &content[indent..];
+        if rest.is_empty() || rest.starts_with('#') {
+            continue;
+        }
+
+        let item = rest == "-" || rest.starts_with("- ");
+        blocks.retain(|(column, _)| if item { *column <= indent } else { *column < indent });
+        let mut column = indent;
+        if item {
+            let stripped = rest[1..].trim_start_matches(' ');
+            column += rest.len() - stripped.len();
+            rest = stripped;
+        }
+
+        let Some((key, value)) = rest.split_once(':') else {
+            continue;
+        };
+        if !(value.is_empty() || value.starts_with(' ')) || key.contains(' ') {
+            continue;
+        }
+        let value = value.split(" #").next().unwrap_or_default();
+        let trimmed = value.trim();
+        let unquoted = trimmed.trim_matches(['"', '\'']);
+        let quote = (trimmed.len() - unquoted.len()) / 2;
+        let start = column + key.len() + 1 + (value.len() - value.trim_start().len()) + quote;
+        let key = key.trim_matches(['"', '\'']);
+
+        let parent = blocks.last().map(|(_, key)| *key);
+        if unquoted.is_empty() {
+            blocks.push((column, key));
+        }
+        if key == "workflowId" && parent == Some("workflows") {
+            workflow = Some(unquoted);
+        }
+
+        entries.push(Entry {
+            line,
+            content,
+            start,
+            key,
+            value: unquoted,
+            parent,
+            workflow,
+        });
+    }
+
+    entries
+}
+
+/// The workflow the given line belongs to.
+fn workflow_at<'a>(entries: &[Entry<'a>], line: usize) -> Option<&'a str> {
+    entries.iter().take_while(|entry| entry.line <= line).last().and_then(|entry| entry.workflow)
+}
+
+/// The step id of the `$steps.<id>` expression under the cursor.
+fn step_reference_at(content: &str, offset: usize) -> Option<&str> {
+    content.match_indices(STEPS_PREFIX).find_map(|(start, prefix)| {
+        let id_start = start + prefix.len();
+        let id_end = content[id_start..].find(|c| !is_id_char(c)).map_or(content.len(), |end| {
+            id_start + end
+        });
+        let id = &content[id_start..id_end];
+        (!id.is_empty() && (start..=id_end).contains(&offset)).then_some(id)
+    })
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn position(params: &Value) -> (usize, usize) {
+    let position = &params["position"];
+    let line = position["line"].as_u64().unwrap_or_default();
+    let character = position["character"].as_u64().unwrap_or_default();
+
+    (line as usize, character as usize)
+}
+
+/// LSP positions count UTF-16 code units; converts one into a byte offset.
+fn byte_offset(content: &str, character: usize) -> usize {
+    let mut units = 0;
+    for (offset, c) in content.char_indices() {
+        if units >= character {
+            return offset;
+        }
+        units += c.len_utf16();
+    }
+
+    content.len()
+}
+
+fn line_range(line: usize, content: &str, start: usize, end: usize) -> Value {
+    let character = |offset: usize| content[..offset].encode_utf16().count();
+
+    json!({
+        "start": { "line": line, "character": character(start) },
+        "end": { "line": line, "character": character(end) },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "file:///workflows.arazzo.yml";
+
+    fn open(sut: &mut Server, text: &str) -> Vec<Value> {
+        sut.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": URI, "languageId": "yaml", "version": 1, "text": text },
+            },
+        }))
+    }
+
+    fn request(sut: &mut Server, method: &str, params: Value) -> Value {
+        let mut replies = sut.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }));
+        assert_eq!(1, replies.len());
+        replies.remove(0)
+    }
+
+    fn at(line: usize, character: usize) -> Value {
+        json!({
+            "textDocument": { "uri": URI },
+            "position": { "line": line, "character": character },
+        })
+    }
+
+    const DOCUMENT: &str = concat!(
+        "arazzo: 1.0.1\n",
+        "info:\n",
+        "  title: Payments\n",
+        "  version: 1.0.0\n",
+        "workflows:\n",
+        "  - workflowId: pay\n",
+        "    steps:\n",
+        "      - stepId: authorize\n",
+        "        operationId: createAuthorization\n",
+        "        outputs:\n",
+        "          authorizationId: $response.body#/id\n",
+        "        onSuccess:\n",
+        "          - name: next\n",
+        "            type: goto\n",
+        "            stepId: capture\n",
+        "      - stepId: capture\n",
+        "        operationId: createCapture\n",
+        "        parameters:\n",
+        "          - name: id\n",
+        "            in: path\n",
+        "            value: $steps.authorize.outputs.\n",
+        "        onFailure:\n",
+        "          - name: refund\n",
+        "            type: goto\n",
+        "            workflowId: refund\n",
+    );
+
+    #[test]
+    fn publish_diagnostics_on_open() {
+        let mut sut = Server::default();
+
+        let actual = open(&mut sut, DOCUMENT);
+
+        let expected = vec![publish_diagnostics(
+            URI,
+            vec![
+                diagnostic(
+                    line_range(15, "      - stepId: capture", 16, 23),
+                    "invalid runtime expression '$steps.authorize.outputs.': empty name",
+                ),
+                diagnostic(
+                    line_range(24, "            workflowId: refund", 24, 30),
+                    "unknown workflow 'refund' in action 'refund'",
+                ),
+            ],
+        )];
+
+        println!("{}", serde_json::to_string_pretty(&actual).unwrap());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn publish_parse_error() {
+        let mut sut = Server::default();
+
+        let actual = open(&mut sut, "arazzo: 1.0.1\ninfo: [\n");
+
+        let diagnostics = &actual[0]["params"]["diagnostics"];
+        assert_eq!(1, diagnostics.as_array().unwrap().len());
+        assert!(diagnostics[0]["message"].as_str().unwrap().starts_with("Failed to parse YAML"));
+    }
+
+    #[test]
+    fn goto_step_definition() {
+        let mut sut = Server::default();
+        open(&mut sut, DOCUMENT);
+
+        let from_action = request(&mut sut, "textDocument/definition", at(14, 22));
+        let from_expression = request(&mut sut, "textDocument/definition", at(20, 24));
+
+        let capture = line_range(15, "      - stepId: capture", 16, 23);
+        let authorize = line_range(7, "      - stepId: authorize", 16, 25);
+
+        assert_eq!(json!({ "uri": URI, "range": capture }), from_action["result"]);
+        assert_eq!(json!({ "uri": URI, "range": authorize }), from_expression["result"]);
+    }
+
+    #[test]
+    fn complete_step_expressions() {
+        let mut sut = Server::default();
+        open(&mut sut, &DOCUMENT.replace("outputs.\n", "outputs.authorizationId\n"));
+        let mut complete = |character| {
+            request(&mut sut, "textDocument/completion", at(20, character))["result"].clone()
+        };
+
+        let steps = complete(26);
+        let properties = complete(36);
+        let outputs = complete(44);
+
+        let step_ids = json!([
+            { "label": "authorize", "kind": 18 },
+            { "label": "capture", "kind": 18 },
+        ]);
+
+        assert_eq!(step_ids, steps);
+        assert_eq!(json!([{ "label": "outputs", "kind": 10 }]), properties);
+        assert_eq!(json!([{ "label": "authorizationId", "kind": 5 }]), outputs);
+    }
+
+    #[test]
+    fn render_preview() {
+        let mut sut = Server::default();
+        open(&mut sut, &std::fs::read_to_string("fixtures/minimal.yml").unwrap());
+
+        let actual = request(
+            &mut sut,
+            "arazzo/render",
+            json!({ "textDocument": { "uri": URI }, "direction": "LR", "workflowIds": ["w"] }),
+        );
+
+        let expected = concat!(
+            "---\n",
+            "title: t\n",
+            "---\n",
+            "flowchart LR\n",
+            "    subgraph w\n",
+            "    w_s --> wEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual["result"]["mermaid"]);
+    }
+
+    #[test]
+    fn run_over_framed_messages() {
+        let messages = [
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ];
+        let input = messages
+            .iter()
+            .map(|message| {
+                let content = message.to_string();
+                format!("Content-Length: {}\r\n\r\n{}", content.len(), content)
+            })
+            .collect::<String>();
+        let mut output = Vec::new();
+
+        run(input.as_bytes(), &mut output).unwrap();
+
+        let actual = String::from_utf8(output).unwrap();
+
+        let shutdown = "{\"jsonrpc\":\"2.0\",\"id\":2,\"result\":null}";
+        let expected = format!("Content-Length: {}\r\n\r\n{}", shutdown.len(), shutdown);
+
+        println!("{}", actual);
+        assert!(actual.starts_with("Content-Length: "), "{actual}");
+        assert!(actual.contains("\"definitionProvider\":true"), "{actual}");
+        assert!(actual.ends_with(&expected), "{actual}");
+    }
+}
@@ -1,23 +1,43 @@
 use std::error::Error;
 use std::fmt::{self, Display};
 use std::io::{self, Read, Write};
+use std::path::Path;
 use std::{fs, process};
 
 use base64::prelude::*;
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
 use serde::Serialize;
 
-use crate::renderer::{MermaidFlowchart, Renderer};
-
-mod arazzo;
-mod renderer;
+use arazzo2mermaid::composition::ArazzoSources;
+use arazzo2mermaid::from_mermaid::{self, FromMermaidError};
+use arazzo2mermaid::mdbook::{self, MdBookError};
+use arazzo2mermaid::openapi::OpenApiSources;
+use arazzo2mermaid::paths::{self, WorkflowPaths};
+use arazzo2mermaid::renderer::coverage::Coverage;
+use arazzo2mermaid::renderer::dataflow::DataFlowDiagram;
+use arazzo2mermaid::renderer::diff::{self, Diff};
+use arazzo2mermaid::renderer::MermaidFlowchart;
+use arazzo2mermaid::renderer::html::HtmlReport;
+use arazzo2mermaid::renderer::markdown::MarkdownDocument;
+use arazzo2mermaid::renderer::trace::Trace;
+#[cfg(feature = "http")]
+use arazzo2mermaid::runner;
+use arazzo2mermaid::simulate::{self, Scenario, Simulation};
+use arazzo2mermaid::{
+    ArazzoDocument, ClassDefs, CompositionError, CoverageError, Direction, FlowchartOptions,
+    OpenApiError, ParseError, Renderer, SimulationError, StepLabel, condition, expression, lsp,
+};
 
 /// Convert Arazzo workflows into Mermaid diagrams.
 #[derive(Parser)]
-#[command(version)]
+#[command(version, args_conflicts_with_subcommands = true)]
 struct Arazzo2Mermaid {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Arazzo workflows file to convert to Mermaid diagrams
     file: Option<String>,
 
@@ -25,13 +45,267 @@ struct Arazzo2Mermaid {
     #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = Format::Yaml)]
     format: Format,
 
-    /// Save to specified file
+    /// Output format to render
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = FormatOut::Mermaid)]
+    format_out: FormatOut,
+
+    /// mermaid.js bundle to inline into HTML output, such as dist/mermaid.min.js from the
+    /// mermaid npm package. Required with --format-out html, which loads nothing from a CDN
+    #[arg(long, value_name = "FILE")]
+    mermaid_js: Option<String>,
+
+    /// Render only the specified workflow (repeatable)
+    #[arg(short, long = "workflow", value_name = "WORKFLOW_ID")]
+    workflows: Vec<String>,
+
+    /// Also render workflows reached from the specified ones within the given depth
+    #[arg(long, value_name = "DEPTH", default_value_t = 0, requires = "workflows")]
+    depth: usize,
+
+    /// Flowchart direction (TD, LR, BT or RL)
+    #[arg(long, value_name = "DIRECTION")]
+    direction: Option<Direction>,
+
+    /// Step node label: description, operation, or a template such as "{stepId}: {operation}"
+    #[arg(long, value_name = "LABEL")]
+    label: Option<StepLabel>,
+
+    /// Mermaid theme to apply through an init directive
+    #[arg(long, value_name = "THEME")]
+    theme: Option<String>,
+
+    /// Apply the default classDef styles to every node
+    #[arg(long, default_value_t = false)]
+    styled: bool,
+
+    /// Load local OpenAPI and Arazzo source descriptions to resolve and validate references
+    #[arg(long, default_value_t = false)]
+    resolve_sources: bool,
+
+    /// Render workflows of other Arazzo documents as subgraphs instead of collapsed nodes
+    #[arg(long, default_value_t = false, requires = "resolve_sources")]
+    expand_external: bool,
+
+    /// Check runtime expressions for syntax errors and unknown steps, inputs and outputs
+    #[arg(long, default_value_t = false)]
+    validate: bool,
+
+    /// Evaluate every simple criterion against a context JSON file instead of rendering
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["split", "live", "print_url", "mermaid_js"]
+    )]
+    context: Option<String>,
+
+    /// Highlight the steps and branches a run took, from a JSON list of step events
+    #[arg(long, value_name = "FILE")]
+    trace: Option<String>,
+
+    /// Colour nodes and label branches from aggregated execution counts (JSON or CSV)
+    #[arg(long, value_name = "FILE", conflicts_with = "trace")]
+    coverage: Option<String>,
+
+    /// Rendering options file (direction, theme, themeVariables, classDefs)
+    #[arg(long, value_name = "FILE")]
+    config: Option<String>,
+
+    /// Save to specified file (a directory with --split)
     #[arg(short, long, value_name = "FILE")]
     output: Option<String>,
 
+    /// Render each workflow as its own Mermaid diagram
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["live", "print_url", "mermaid_js"]
+    )]
+    split: bool,
+
     /// Open in mermaid.live
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, conflicts_with = "mermaid_js")]
     live: bool,
+
+    /// Print a shareable URL instead of the Mermaid text
+    #[arg(
+        long,
+        value_name = "TARGET",
+        value_enum,
+        conflicts_with_all = ["live", "output", "mermaid_js"]
+    )]
+    print_url: Option<UrlTarget>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every distinct path from the first step to the end of each workflow
+    Paths(PathsArgs),
+    /// Run a workflow offline against mocked responses and print the steps it takes
+    Simulate(SimulateArgs),
+    /// Compare two versions of an Arazzo document and render their combined flowchart
+    Diff(DiffArgs),
+    /// Run as an mdBook preprocessor rendering ```arazzo blocks and {{#arazzo}} directives
+    Mdbook(MdbookArgs),
+    /// Convert a Mermaid flowchart into an Arazzo document skeleton
+    FromMermaid(FromMermaidArgs),
+    /// Run a language server over stdio with diagnostics, navigation and a preview request
+    Lsp,
+    /// Run a workflow against a live API, sending each step as an HTTP request
+    #[cfg(feature = "http")]
+    Run(RunArgs),
+}
+
+#[derive(Args)]
+struct PathsArgs {
+    /// Arazzo workflows file to enumerate paths of
+    file: Option<String>,
+
+    /// Input file format to read
+    #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = Format::Yaml)]
+    format: Format,
+
+    /// Output format to write
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = PathsFormat::Json)]
+    format_out: PathsFormat,
+
+    /// Enumerate only the specified workflow (repeatable)
+    #[arg(short, long = "workflow", value_name = "WORKFLOW_ID")]
+    workflows: Vec<String>,
+
+    /// How many times a path may enter the same step, bounding loops and retries
+    #[arg(long, value_name = "COUNT", default_value_t = 2)]
+    max_visits: usize,
+
+    /// Save to specified file
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct SimulateArgs {
+    /// Arazzo workflows file to simulate
+    file: Option<String>,
+
+    /// Scenario file (YAML or JSON) with the inputs and mocked responses per step
+    #[arg(long, value_name = "FILE")]
+    scenario: String,
+
+    /// Input file format to read
+    #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = Format::Yaml)]
+    format: Format,
+
+    /// Output format to write
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = SimulateFormat::Text)]
+    format_out: SimulateFormat,
+
+    /// Workflow to run, overriding the scenario's workflowId
+    #[arg(short, long, value_name = "WORKFLOW_ID")]
+    workflow: Option<String>,
+
+    /// Save to specified file
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// Previous version of the Arazzo workflows file
+    old: String,
+
+    /// New version of the Arazzo workflows file
+    new: String,
+
+    /// Input file format to read
+    #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = Format::Yaml)]
+    format: Format,
+
+    /// Output format to write
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = DiffFormat::Mermaid)]
+    format_out: DiffFormat,
+
+    /// Save to specified file
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct FromMermaidArgs {
+    /// Mermaid flowchart file to convert
+    file: Option<String>,
+
+    /// Output format to write
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = Format::Yaml)]
+    format_out: Format,
+
+    /// Save to specified file
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct MdbookArgs {
+    #[command(subcommand)]
+    command: Option<MdbookCommand>,
+}
+
+#[derive(Subcommand)]
+enum MdbookCommand {
+    /// Tell mdBook whether the renderer is supported (every renderer is)
+    Supports { renderer: String },
+}
+
+#[derive(clap::ValueEnum, Clone)]
+enum DiffFormat {
+    Mermaid,
+    Text,
+    Json,
+}
+
+#[cfg(feature = "http")]
+#[derive(Args)]
+struct RunArgs {
+    /// Arazzo workflows file to run
+    file: Option<String>,
+
+    /// Send requests to this URL instead of the servers of the OpenAPI descriptions
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Workflow inputs file (YAML or JSON)
+    #[arg(long, value_name = "FILE")]
+    inputs: Option<String>,
+
+    /// Input file format to read
+    #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = Format::Yaml)]
+    format: Format,
+
+    /// Output format to write
+    #[arg(long, value_name = "FORMAT", value_enum, default_value_t = SimulateFormat::Text)]
+    format_out: SimulateFormat,
+
+    /// Workflow to run instead of the first one
+    #[arg(short, long, value_name = "WORKFLOW_ID")]
+    workflow: Option<String>,
+
+    /// Seconds to wait for each response
+    #[arg(long, value_name = "SECONDS", default_value_t = 30)]
+    timeout: u64,
+
+    /// Save to specified file
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+#[derive(clap::ValueEnum, Clone)]
+enum SimulateFormat {
+    Text,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone)]
+enum PathsFormat {
+    Json,
+    Markdown,
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -40,25 +314,94 @@ enum Format {
     Json,
 }
 
+#[derive(clap::ValueEnum, Clone)]
+enum FormatOut {
+    Mermaid,
+    /// Self-contained HTML report, needs --mermaid-js
+    Html,
+    Markdown,
+    DataFlow,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum UrlTarget {
+    /// mermaid.live editor
+    LiveEdit,
+    /// mermaid.live read-only view
+    LiveView,
+    /// mermaid.ink SVG image
+    InkSvg,
+    /// mermaid.ink PNG image
+    InkPng,
+}
+
 #[derive(Debug)]
 enum Arazzo2MermaidError {
     Io(io::Error),
-    Yaml(yaml_serde::Error),
+    Parse(ParseError),
     Json(serde_json::Error),
     Deflate(io::Error),
     Open(io::Error),
+    UnknownWorkflow(String),
+    Config(yaml_serde::Error),
+    OpenApi(OpenApiError),
+    Composition(CompositionError),
+    UnresolvedOperations(Vec<String>),
+    InvalidExpressions(Vec<String>),
+    Trace(serde_json::Error),
+    UnknownTraceSteps(Vec<String>),
+    Coverage(CoverageError),
+    Scenario(yaml_serde::Error),
+    Simulation(SimulationError),
+    MdBook(MdBookError),
+    FromMermaid(FromMermaidError),
+    WriteYaml(yaml_serde::Error),
+    #[cfg(feature = "http")]
+    Inputs(yaml_serde::Error),
+    #[cfg(feature = "http")]
+    RunFailed(String, String),
 }
 
 impl Display for Arazzo2MermaidError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Arazzo2MermaidError::Io(error) => write!(f, "Failed to read or write file: {}", error),
-            Arazzo2MermaidError::Yaml(error) => write!(f, "Failed to parse YAML: {}", error),
+            Arazzo2MermaidError::Parse(error) => write!(f, "{}", error),
             Arazzo2MermaidError::Json(error) => write!(f, "Failed to parse JSON: {}", error),
             Arazzo2MermaidError::Deflate(error) => {
                 write!(f, "Failed to compress for mermaid.live: {}", error)
             }
             Arazzo2MermaidError::Open(error) => write!(f, "Failed to open browser: {}", error),
+            Arazzo2MermaidError::UnknownWorkflow(workflow_id) => {
+                write!(f, "Workflow not found: {}", workflow_id)
+            }
+            Arazzo2MermaidError::Config(error) => write!(f, "Failed to parse config: {}", error),
+            Arazzo2MermaidError::OpenApi(error) => write!(f, "{}", error),
+            Arazzo2MermaidError::Composition(error) => write!(f, "{}", error),
+            Arazzo2MermaidError::UnresolvedOperations(problems) => {
+                write!(f, "Failed to resolve operations:\n  {}", problems.join("\n  "))
+            }
+            Arazzo2MermaidError::InvalidExpressions(problems) => {
+                write!(f, "Invalid runtime expressions:\n  {}", problems.join("\n  "))
+            }
+            Arazzo2MermaidError::Trace(error) => write!(f, "Failed to parse trace: {}", error),
+            Arazzo2MermaidError::UnknownTraceSteps(steps) => {
+                write!(f, "Trace steps not found:\n  {}", steps.join("\n  "))
+            }
+            Arazzo2MermaidError::Coverage(error) => write!(f, "{}", error),
+            Arazzo2MermaidError::Scenario(error) => {
+                write!(f, "Failed to parse scenario: {}", error)
+            }
+            Arazzo2MermaidError::Simulation(error) => write!(f, "{}", error),
+            Arazzo2MermaidError::MdBook(error) => write!(f, "{}", error),
+            Arazzo2MermaidError::FromMermaid(error) => write!(f, "{}", error),
+            Arazzo2MermaidError::WriteYaml(error) => write!(f, "Failed to write YAML: {}", error),
+            #[cfg(feature = "http")]
+            Arazzo2MermaidError::Inputs(error) => write!(f, "Failed to parse inputs: {}", error),
+            #[cfg(feature = "http")]
+            Arazzo2MermaidError::RunFailed(workflow_id, end) => {
+                write!(f, "Workflow {} {}", workflow_id, end)
+            }
         }
     }
 }
@@ -74,34 +417,177 @@ struct MermaidLive {
 fn main() {
     let cli = Arazzo2Mermaid::parse();
 
-    let reader: Box<dyn Read> = match cli.file.as_deref() {
-        Some("-") | None => Box::new(io::stdin()),
-        Some(file) => match fs::File::open(file) {
-            Ok(file) => Box::new(file),
+    if let Some(command) = &cli.command {
+        let result = match command {
+            Command::Paths(args) => write_paths(args),
+            Command::Simulate(args) => write_simulation(args),
+            Command::Diff(args) => write_diff(args),
+            Command::Mdbook(args) => write_mdbook(args),
+            Command::FromMermaid(args) => write_from_mermaid(args),
+            Command::Lsp => lsp::run(io::stdin().lock(), io::stdout().lock())
+                .map_err(Arazzo2MermaidError::Io),
+            #[cfg(feature = "http")]
+            Command::Run(args) => write_run(args),
+        };
+        if let Err(error) = result {
+            eprint_and_exit(Box::new(error));
+        }
+
+        return;
+    }
+
+    let reader = match open_reader(cli.file.as_deref()) {
+        Ok(reader) => reader,
+        Err(error) => {
+            eprint_and_exit(Box::new(error));
+        }
+    };
+
+    let options = match load_options(&cli) {
+        Ok(options) => options,
+        Err(error) => {
+            eprint_and_exit(Box::new(error));
+        }
+    };
+
+    if cli.split && !matches!(cli.format_out, FormatOut::Mermaid) {
+        Arazzo2Mermaid::command()
+            .error(ErrorKind::ArgumentConflict, "--split requires --format-out mermaid")
+            .exit();
+    }
+
+    if (cli.live || cli.print_url.is_some())
+        && !matches!(cli.format_out, FormatOut::Mermaid | FormatOut::DataFlow)
+    {
+        let message = "--live and --print-url require --format-out mermaid or data-flow";
+        Arazzo2Mermaid::command().error(ErrorKind::ArgumentConflict, message).exit();
+    }
+
+    if matches!(cli.format_out, FormatOut::Html) && cli.mermaid_js.is_none() {
+        let message = concat!(
+            "--format-out html requires --mermaid-js <FILE>\n\n",
+            "The HTML report inlines mermaid.js instead of loading it from a CDN. ",
+            "Download the mermaid package (for example `npm pack mermaid`) and pass ",
+            "its dist/mermaid.min.js.",
+        );
+        Arazzo2Mermaid::command().error(ErrorKind::MissingRequiredArgument, message).exit();
+    }
+
+    let arazzo = match parse(reader, &cli.format, &cli.workflows) {
+        Ok(arazzo) => arazzo,
+        Err(error) => {
+            eprint_and_exit(Box::new(error));
+        }
+    };
+
+    if cli.validate {
+        let problems = expression::validate(&arazzo);
+        if !problems.is_empty() {
+            eprint_and_exit(Box::new(Arazzo2MermaidError::InvalidExpressions(problems)));
+        }
+    }
+
+    if let Some(context) = cli.context.as_deref() {
+        if let Err(error) = write_evaluation(&arazzo, context, cli.output.as_deref()) {
+            eprint_and_exit(Box::new(error));
+        }
+
+        return;
+    }
+
+    let (sources, external_workflows) = if cli.resolve_sources {
+        match load_sources(&arazzo, cli.file.as_deref()) {
+            Ok(sources) => sources,
             Err(error) => {
-                eprint_and_exit(Box::new(Arazzo2MermaidError::Io(error)));
+                eprint_and_exit(Box::new(error));
             }
-        },
+        }
+    } else {
+        (OpenApiSources::default(), ArazzoSources::default())
     };
 
-    match run(reader, &cli.format) {
-        Ok(mermaid) => {
-            if cli.live {
-                if let Err(error) = open_mermaid_live(&mermaid) {
-                    eprint_and_exit(Box::new(error));
-                }
-            } else if let Some(file) = cli.output.as_deref() {
-                if let Err(error) = fs::write(file, mermaid) {
+    let trace = match cli.trace.as_deref().map(|file| load_trace(&arazzo, file)).transpose() {
+        Ok(trace) => trace,
+        Err(error) => {
+            eprint_and_exit(Box::new(error));
+        }
+    };
+
+    let coverage = match cli.coverage.as_deref().map(load_coverage).transpose() {
+        Ok(coverage) => coverage,
+        Err(error) => {
+            eprint_and_exit(Box::new(error));
+        }
+    };
+
+    let has_coverage = coverage.is_some();
+    let expand_external = options.expand_external;
+    let mut builder = MermaidFlowchart::builder()
+        .external_workflows(external_workflows, expand_external)
+        .options(options)
+        .sources(sources);
+    if !cli.workflows.is_empty() {
+        builder = builder.workflows(cli.workflows.clone(), cli.depth);
+    }
+    if let Some(trace) = trace {
+        builder = builder.trace(trace);
+    }
+    if let Some(coverage) = coverage {
+        builder = builder.coverage(coverage);
+    }
+    let flowchart = builder.build();
+
+    // Other outputs draw the flowchart per workflow or inside another document, so
+    // the summary comes from its own render there.
+    let embedded = cli.split || !matches!(cli.format_out, FormatOut::Mermaid);
+    if has_coverage && embedded {
+        let (_, summary) = flowchart.render_with_coverage_summary(&arazzo);
+        eprint!("{}", summary);
+    }
+
+    if cli.split {
+        if let Err(error) = write_split(&flowchart, &arazzo, cli.output.as_deref()) {
+            eprint_and_exit(Box::new(error));
+        }
+
+        return;
+    }
+
+    let output = match cli.format_out {
+        FormatOut::Mermaid => {
+            let (output, summary) = flowchart.render_with_coverage_summary(&arazzo);
+            eprint!("{}", summary);
+            output
+        }
+        FormatOut::Markdown => MarkdownDocument { flowchart }.render(&arazzo),
+        FormatOut::DataFlow => DataFlowDiagram { flowchart }.render(&arazzo),
+        FormatOut::Html => {
+            let mermaid_js = cli.mermaid_js.as_deref().unwrap_or_default();
+            match fs::read_to_string(mermaid_js) {
+                Ok(mermaid_js) => HtmlReport { flowchart, mermaid_js }.render(&arazzo),
+                Err(error) => {
                     eprint_and_exit(Box::new(Arazzo2MermaidError::Io(error)));
                 }
-            } else {
-                print!("{}", mermaid);
             }
         }
-        Err(error) => {
+    };
+
+    if cli.live {
+        if let Err(error) = open_mermaid_live(&output) {
             eprint_and_exit(Box::new(error));
         }
-    };
+    } else if let Some(target) = cli.print_url {
+        match mermaid_url(&output, target) {
+            Ok(url) => println!("{}", url),
+            Err(error) => eprint_and_exit(Box::new(error)),
+        }
+    } else if let Some(file) = cli.output.as_deref() {
+        if let Err(error) = fs::write(file, output) {
+            eprint_and_exit(Box::new(Arazzo2MermaidError::Io(error)));
+        }
+    } else {
+        print!("{}", output);
+    }
 }
 
 fn eprint_and_exit(error: Box<dyn Error>) -> ! {
@@ -109,22 +595,359 @@ fn eprint_and_exit(error: Box<dyn Error>) -> ! {
     process::exit(1);
 }
 
-fn run(mut reader: impl Read, format: &Format) -> Result<String, Arazzo2MermaidError> {
+fn open_reader(file: Option<&str>) -> Result<Box<dyn Read>, Arazzo2MermaidError> {
+    match file {
+        Some("-") | None => Ok(Box::new(io::stdin())),
+        Some(file) => Ok(Box::new(fs::File::open(file).map_err(Arazzo2MermaidError::Io)?)),
+    }
+}
+
+fn load_options(cli: &Arazzo2Mermaid) -> Result<FlowchartOptions, Arazzo2MermaidError> {
+    let mut options = match cli.config.as_deref() {
+        Some(file) => {
+            let content = fs::read_to_string(file).map_err(Arazzo2MermaidError::Io)?;
+            yaml_serde::from_str(&content).map_err(Arazzo2MermaidError::Config)?
+        }
+        None => FlowchartOptions::default(),
+    };
+
+    if let Some(direction) = cli.direction {
+        options.direction = direction;
+    }
+    if let Some(step_label) = &cli.label {
+        options.step_label = step_label.clone();
+    }
+    if cli.expand_external {
+        options.expand_external = true;
+    }
+    if let Some(theme) = &cli.theme {
+        options.theme = Some(theme.clone());
+    }
+    if cli.styled && options.class_defs.is_none() {
+        options.class_defs = Some(ClassDefs::default());
+    }
+
+    Ok(options)
+}
+
+fn parse(
+    mut reader: impl Read,
+    format: &Format,
+    workflows: &[String],
+) -> Result<ArazzoDocument, Arazzo2MermaidError> {
     let mut content = String::new();
     reader
         .read_to_string(&mut content)
         .map_err(Arazzo2MermaidError::Io)?;
 
     let arazzo = match format {
-        Format::Yaml => yaml_serde::from_str(&content).map_err(Arazzo2MermaidError::Yaml)?,
-        Format::Json => serde_json::from_str(&content).map_err(Arazzo2MermaidError::Json)?,
+        Format::Yaml => arazzo2mermaid::parse(&content),
+        Format::Json => arazzo2mermaid::parse_json(&content),
+    }
+    .map_err(Arazzo2MermaidError::Parse)?;
+
+    for workflow_id in workflows {
+        if !arazzo.workflows.iter().any(|w| &w.workflow_id == workflow_id) {
+            return Err(Arazzo2MermaidError::UnknownWorkflow(workflow_id.clone()));
+        }
+    }
+
+    Ok(arazzo)
+}
+
+fn load_sources(
+    arazzo: &ArazzoDocument,
+    file: Option<&str>,
+) -> Result<(OpenApiSources, ArazzoSources), Arazzo2MermaidError> {
+    let file = file.filter(|file| *file != "-").map(Path::new);
+    let base_dir = file.and_then(Path::parent).unwrap_or(Path::new("."));
+
+    let sources = OpenApiSources::load(arazzo, base_dir).map_err(Arazzo2MermaidError::OpenApi)?;
+
+    let problems = sources.validate(arazzo);
+    if !problems.is_empty() {
+        return Err(Arazzo2MermaidError::UnresolvedOperations(problems));
+    }
+
+    let external_workflows =
+        ArazzoSources::load(arazzo, file).map_err(Arazzo2MermaidError::Composition)?;
+
+    Ok((sources, external_workflows))
+}
+
+fn load_trace(arazzo: &ArazzoDocument, file: &str) -> Result<Trace, Arazzo2MermaidError> {
+    let content = fs::read_to_string(file).map_err(Arazzo2MermaidError::Io)?;
+    let trace: Trace = serde_json::from_str(&content).map_err(Arazzo2MermaidError::Trace)?;
+
+    let unknown = trace.unknown_steps(arazzo);
+    if !unknown.is_empty() {
+        return Err(Arazzo2MermaidError::UnknownTraceSteps(unknown));
+    }
+
+    Ok(trace)
+}
+
+fn load_coverage(file: &str) -> Result<Coverage, Arazzo2MermaidError> {
+    let content = fs::read_to_string(file).map_err(Arazzo2MermaidError::Io)?;
+
+    let coverage = if file.ends_with(".csv") {
+        Coverage::from_csv(&content)
+    } else {
+        Coverage::from_json(&content)
+    };
+
+    coverage.map_err(Arazzo2MermaidError::Coverage)
+}
+
+fn write_evaluation(
+    arazzo: &ArazzoDocument,
+    context: &str,
+    output: Option<&str>,
+) -> Result<(), Arazzo2MermaidError> {
+    let context = fs::read_to_string(context).map_err(Arazzo2MermaidError::Io)?;
+    let context: serde_json::Value =
+        serde_json::from_str(&context).map_err(Arazzo2MermaidError::Json)?;
+
+    let mut lines = condition::evaluate_criteria(arazzo, &context).join("\n");
+    lines.push('\n');
+
+    match output {
+        Some(file) => fs::write(file, lines).map_err(Arazzo2MermaidError::Io),
+        None => {
+            print!("{}", lines);
+            Ok(())
+        }
+    }
+}
+
+fn write_paths(args: &PathsArgs) -> Result<(), Arazzo2MermaidError> {
+    let reader = open_reader(args.file.as_deref())?;
+    let arazzo = parse(reader, &args.format, &args.workflows)?;
+
+    let workflows = arazzo
+        .workflows
+        .iter()
+        .filter(|w| args.workflows.is_empty() || args.workflows.contains(&w.workflow_id))
+        .map(|workflow| paths::enumerate(workflow, args.max_visits))
+        .collect::<Vec<WorkflowPaths>>();
+
+    let output = match args.format_out {
+        PathsFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(&workflows).map_err(Arazzo2MermaidError::Json)?;
+            json.push('\n');
+            json
+        }
+        PathsFormat::Markdown => paths::to_markdown(&arazzo.info.title, &workflows),
+    };
+
+    match args.output.as_deref() {
+        Some(file) => fs::write(file, output).map_err(Arazzo2MermaidError::Io),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+fn write_diff(args: &DiffArgs) -> Result<(), Arazzo2MermaidError> {
+    let old = parse(open_reader(Some(&args.old))?, &args.format, &[])?;
+    let new = parse(open_reader(Some(&args.new))?, &args.format, &[])?;
+    let diff = Diff::compare(&old, &new);
+
+    let output = match args.format_out {
+        DiffFormat::Mermaid => {
+            eprint!("{}", diff::to_text(&diff));
+            let merged = diff::merge(old, new);
+            MermaidFlowchart::builder().diff(diff).build().render(&merged)
+        }
+        DiffFormat::Text => diff::to_text(&diff),
+        DiffFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(&diff).map_err(Arazzo2MermaidError::Json)?;
+            json.push('\n');
+            json
+        }
+    };
+
+    match args.output.as_deref() {
+        Some(file) => fs::write(file, output).map_err(Arazzo2MermaidError::Io),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+fn write_from_mermaid(args: &FromMermaidArgs) -> Result<(), Arazzo2MermaidError> {
+    let mut content = String::new();
+    open_reader(args.file.as_deref())?
+        .read_to_string(&mut content)
+        .map_err(Arazzo2MermaidError::Io)?;
+    let skeleton = from_mermaid::convert(&content).map_err(Arazzo2MermaidError::FromMermaid)?;
+
+    let output = match args.format_out {
+        Format::Yaml => yaml_serde::to_string(&skeleton).map_err(Arazzo2MermaidError::WriteYaml)?,
+        Format::Json => {
+            let mut json =
+                serde_json::to_string_pretty(&skeleton).map_err(Arazzo2MermaidError::Json)?;
+            json.push('\n');
+            json
+        }
+    };
+
+    match args.output.as_deref() {
+        Some(file) => fs::write(file, output).map_err(Arazzo2MermaidError::Io),
+        None => {
+            print!("{}", output);
+            Ok(())
+        }
+    }
+}
+
+fn write_mdbook(args: &MdbookArgs) -> Result<(), Arazzo2MermaidError> {
+    if let Some(MdbookCommand::Supports { .. }) = args.command {
+        return Ok(());
+    }
+
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input).map_err(Arazzo2MermaidError::Io)?;
+    let book = mdbook::preprocess(&input).map_err(Arazzo2MermaidError::MdBook)?;
+    print!("{}", book);
+
+    Ok(())
+}
+
+fn write_simulation(args: &SimulateArgs) -> Result<(), Arazzo2MermaidError> {
+    let reader = open_reader(args.file.as_deref())?;
+    let workflows = args.workflow.iter().cloned().collect::<Vec<String>>();
+    let arazzo = parse(reader, &args.format, &workflows)?;
+
+    let content = fs::read_to_string(&args.scenario).map_err(Arazzo2MermaidError::Io)?;
+    let mut scenario: Scenario =
+        yaml_serde::from_str(&content).map_err(Arazzo2MermaidError::Scenario)?;
+    if args.workflow.is_some() {
+        scenario.workflow_id = args.workflow.clone();
+    }
+
+    let simulation =
+        simulate::simulate(&arazzo, &scenario).map_err(Arazzo2MermaidError::Simulation)?;
+
+    write_simulation_output(&simulation, &args.format_out, args.output.as_deref())
+}
+
+#[cfg(feature = "http")]
+fn write_run(args: &RunArgs) -> Result<(), Arazzo2MermaidError> {
+    let reader = open_reader(args.file.as_deref())?;
+    let workflows = args.workflow.iter().cloned().collect::<Vec<String>>();
+    let arazzo = parse(reader, &args.format, &workflows)?;
+
+    let file = args.file.as_deref().filter(|file| *file != "-").map(Path::new);
+    let base_dir = file.and_then(Path::parent).unwrap_or(Path::new("."));
+    let sources = OpenApiSources::load(&arazzo, base_dir).map_err(Arazzo2MermaidError::OpenApi)?;
+
+    let inputs = match args.inputs.as_deref() {
+        Some(file) => {
+            let content = fs::read_to_string(file).map_err(Arazzo2MermaidError::Io)?;
+            yaml_serde::from_str(&content).map_err(Arazzo2MermaidError::Inputs)?
+        }
+        None => serde_json::Map::new(),
+    };
+
+    let timeout = std::time::Duration::from_secs(args.timeout);
+    let mut responder = runner::HttpResponder::new(&sources, args.base_url.clone(), timeout);
+    let simulation = simulate::execute(&arazzo, args.workflow.as_deref(), &inputs, &mut responder)
+        .map_err(Arazzo2MermaidError::Simulation)?;
+
+    write_simulation_output(&simulation, &args.format_out, args.output.as_deref())?;
+
+    if simulation.end != "end" {
+        return Err(Arazzo2MermaidError::RunFailed(simulation.workflow_id, simulation.end));
+    }
+
+    Ok(())
+}
+
+fn write_simulation_output(
+    simulation: &Simulation,
+    format_out: &SimulateFormat,
+    output: Option<&str>,
+) -> Result<(), Arazzo2MermaidError> {
+    let output_text = match format_out {
+        SimulateFormat::Text => simulate::to_text(simulation),
+        SimulateFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(simulation).map_err(Arazzo2MermaidError::Json)?;
+            json.push('\n');
+            json
+        }
     };
-    let mermaid = MermaidFlowchart.render(&arazzo);
 
-    Ok(mermaid)
+    match output {
+        Some(file) => fs::write(file, output_text).map_err(Arazzo2MermaidError::Io),
+        None => {
+            print!("{}", output_text);
+            Ok(())
+        }
+    }
+}
+
+fn write_split(
+    flowchart: &MermaidFlowchart,
+    arazzo: &ArazzoDocument,
+    output: Option<&str>,
+) -> Result<(), Arazzo2MermaidError> {
+    match output {
+        Some(dir) => {
+            let diagrams =
+                flowchart.render_split(arazzo, &|workflow_id| format!("{workflow_id}.mmd"));
+
+            fs::create_dir_all(dir).map_err(Arazzo2MermaidError::Io)?;
+            for (workflow_id, mermaid) in diagrams {
+                let file = Path::new(dir).join(format!("{workflow_id}.mmd"));
+                fs::write(file, mermaid).map_err(Arazzo2MermaidError::Io)?;
+            }
+        }
+        None => {
+            let diagrams =
+                flowchart.render_split(arazzo, &|workflow_id| format!("#{workflow_id}"));
+
+            let blocks = diagrams
+                .iter()
+                .map(|(workflow_id, mermaid)| {
+                    format!("## {workflow_id}\n\n```mermaid\n{mermaid}```\n")
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+            print!("{}", blocks);
+        }
+    }
+
+    Ok(())
 }
 
 fn open_mermaid_live(mermaid: &str) -> Result<(), Arazzo2MermaidError> {
+    let url = mermaid_url(mermaid, UrlTarget::LiveEdit)?;
+
+    open::that(url).map_err(Arazzo2MermaidError::Open)?;
+
+    Ok(())
+}
+
+fn mermaid_url(mermaid: &str, target: UrlTarget) -> Result<String, Arazzo2MermaidError> {
+    let encoded = pako(mermaid)?;
+
+    let url = match target {
+        UrlTarget::LiveEdit => format!("https://mermaid.live/edit#pako:{}", encoded),
+        UrlTarget::LiveView => format!("https://mermaid.live/view#pako:{}", encoded),
+        UrlTarget::InkSvg => format!("https://mermaid.ink/svg/pako:{}", encoded),
+        UrlTarget::InkPng => format!("https://mermaid.ink/img/pako:{}?type=png", encoded),
+    };
+
+    Ok(url)
+}
+
+fn pako(mermaid: &str) -> Result<String, Arazzo2MermaidError> {
     let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
 
     let mermaid_live = MermaidLive { code: mermaid.to_string() };
@@ -137,12 +960,7 @@ fn open_mermaid_live(mermaid: &str) -> Result<(), Arazzo2MermaidError> {
     let compressed_bytes = encoder.finish()
         .map_err(Arazzo2MermaidError::Deflate)?;
 
-    let encoded = BASE64_URL_SAFE_NO_PAD.encode(compressed_bytes);
-
-    open::that(format!("https://mermaid.live/edit#pako:{}", encoded))
-        .map_err(Arazzo2MermaidError::Open)?;
-
-    Ok(())
+    Ok(BASE64_URL_SAFE_NO_PAD.encode(compressed_bytes))
 }
 
 #[cfg(test)]
@@ -151,7 +969,7 @@ mod tests {
     use std::io::Cursor;
 
     #[test]
-    fn run_read_stdin() {
+    fn parse_read_stdin() {
         let mut reader = fs::File::open("fixtures/minimal.yml").unwrap();
 
         let mut content = String::new();
@@ -159,38 +977,84 @@ mod tests {
 
         let reader = Cursor::new(content);
 
-        run(reader, &Format::Yaml).unwrap();
+        parse(reader, &Format::Yaml, &[]).unwrap();
     }
 
     #[test]
-    fn run_read_yml_file() {
+    fn parse_read_yml_file() {
         let reader = fs::File::open("fixtures/minimal.yml").unwrap();
 
-        run(reader, &Format::Yaml).unwrap();
+        parse(reader, &Format::Yaml, &[]).unwrap();
     }
 
     #[test]
-    fn run_read_invalid_yaml() {
+    fn parse_read_invalid_yaml() {
         let reader = Cursor::new("invalid yaml");
 
-        let actual = run(reader, &Format::Yaml).is_err();
+        let actual = parse(reader, &Format::Yaml, &[]).is_err();
 
         assert!(actual);
     }
 
     #[test]
-    fn run_read_json_file() {
+    fn parse_read_json_file() {
         let reader = fs::File::open("fixtures/minimal.json").unwrap();
 
-        run(reader, &Format::Json).unwrap();
+        parse(reader, &Format::Json, &[]).unwrap();
     }
 
     #[test]
-    fn run_read_invalid_json() {
+    fn parse_read_invalid_json() {
         let reader = Cursor::new("invalid json");
 
-        let actual = run(reader, &Format::Json).is_err();
+        let actual = parse(reader, &Format::Json, &[]).is_err();
 
         assert!(actual);
     }
+
+    #[test]
+    fn print_url_conflicts() {
+        let actual = ["--live", "-o=flowchart.mmd"].map(|flag| {
+            let args = ["arazzo2mermaid", "arazzo.yml", "--print-url", "live-edit", flag];
+            Arazzo2Mermaid::try_parse_from(args).map(|_| ()).map_err(|error| error.kind())
+        });
+
+        let expected = [Err(ErrorKind::ArgumentConflict), Err(ErrorKind::ArgumentConflict)];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn mermaid_url_targets() {
+        let encoded = pako("flowchart TD\n").unwrap();
+
+        let actual = [
+            UrlTarget::LiveEdit,
+            UrlTarget::LiveView,
+            UrlTarget::InkSvg,
+            UrlTarget::InkPng,
+        ]
+        .map(|target| mermaid_url("flowchart TD\n", target).unwrap());
+
+        let expected = [
+            format!("https://mermaid.live/edit#pako:{}", encoded),
+            format!("https://mermaid.live/view#pako:{}", encoded),
+            format!("https://mermaid.ink/svg/pako:{}", encoded),
+            format!("https://mermaid.ink/img/pako:{}?type=png", encoded),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn pako_round_trip() {
+        let encoded = pako("flowchart TD\n").unwrap();
+
+        let compressed = BASE64_URL_SAFE_NO_PAD.decode(encoded).unwrap();
+        let mut decoder = flate2::read::ZlibDecoder::new(compressed.as_slice());
+        let mut actual = String::new();
+        decoder.read_to_string(&mut actual).unwrap();
+
+        assert_eq!("{\"code\":\"flowchart TD\\n\"}", actual);
+    }
 }
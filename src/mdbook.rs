@@ -0,0 +1,361 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::renderer::{Direction, MermaidFlowchart, Renderer, StepLabel};
+
+const DIRECTIVE: &str = "{{#arazzo ";
+
+#[derive(Debug, PartialEq)]
+pub struct MdBookError(String);
+
+impl Display for MdBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to preprocess book: {}", self.0)
+    }
+}
+
+impl Error for MdBookError {}
+
+/// Runs as an mdBook preprocessor: reads the `[context, book]` pair mdBook
+/// writes to stdin and returns the book with every ```` ```arazzo ```` block and
+/// `{{#arazzo file}}` directive replaced by a ```` ```mermaid ```` flowchart.
+pub fn preprocess(input: &str) -> Result<String, MdBookError> {
+    let (context, mut book): (Value, Value) = serde_json::from_str(input)
+        .map_err(|error| MdBookError(format!("invalid input: {error}")))?;
+
+    let root = PathBuf::from(context["root"].as_str().unwrap_or("."));
+    let src = context["config"]["book"]["src"].as_str().unwrap_or("src");
+    preprocess_items(&mut book, &root.join(src))?;
+
+    serde_json::to_string(&book).map_err(|error| MdBookError(error.to_string()))
+}
+
+/// Walks the book's sections, whatever the mdBook version names the lists.
+fn preprocess_items(value: &mut Value, src_dir: &Path) -> Result<(), MdBookError> {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::Object(chapter)) = object.get_mut("Chapter") {
+                let path = chapter.get("path").and_then(Value::as_str).map(PathBuf::from);
+                let base_dir = match path.as_deref().and_then(Path::parent) {
+                    Some(parent) => src_dir.join(parent),
+                    None => src_dir.to_path_buf(),
+                };
+                if let Some(Value::String(content)) = chapter.get_mut("content") {
+                    *content = preprocess_chapter(content, &base_dir).map_err(|error| {
+                        let name = chapter_name(path.as_deref());
+                        MdBookError(format!("{name}: {}", error.0))
+                    })?;
+                }
+            }
+            for child in object.values_mut() {
+                preprocess_items(child, src_dir)?;
+            }
+        }
+        Value::Array(values) => {
+            for child in values {
+                preprocess_items(child, src_dir)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn chapter_name(path: Option<&Path>) -> String {
+    path.map_or(String::from("chapter"), |path| path.display().to_string())
+}
+
+/// Replaces the Arazzo blocks and directives of a chapter. Directive paths are
+/// relative to `base_dir`, the chapter's directory. Other code blocks are left
+/// alone, so they can show the syntax.
+pub fn preprocess_chapter(content: &str, base_dir: &Path) -> Result<String, MdBookError> {
+    let mut output = String::new();
+    let mut lines = content.split_inclusive('\n');
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let indentation = &line[..line.len() - trimmed.len()];
+        let fence_length = trimmed.chars().take_while(|c| *c == '`').count();
+        if fence_length < 3 {
+            output.push_str(&replace_directives(line, base_dir)?);
+            continue;
+        }
+
+        let fence = &trimmed[..fence_length];
+        let info = trimmed[fence_length..].trim();
+        let mut body = String::new();
+        let mut closing = None;
+        for line in lines.by_ref() {
+            if line.trim() == fence {
+                closing = Some(line);
+                break;
+            }
+            body.push_str(line);
+        }
+
+        match info.split_whitespace().next() {
+            Some("arazzo") => {
+                let options = info.strip_prefix("arazzo").unwrap_or_default();
+                let document = outdent(&body, indentation.len());
+                output.push_str(&indent(&mermaid_block(&document, options)?, indentation));
+            }
+            _ => {
+                output.push_str(line);
+                output.push_str(&body);
+                output.push_str(closing.unwrap_or_default());
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn replace_directives(line: &str, base_dir: &Path) -> Result<String, MdBookError> {
+    let mut output = String::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find(DIRECTIVE) {
+        let Some(length) = rest[start..].find("}}") else {
+            break;
+        };
+        let before = &rest[..start];
+
+        let arguments = &rest[start + DIRECTIVE.len()..start + length];
+        let (file, options) = arguments.trim().split_once(' ').unwrap_or((arguments.trim(), ""));
+        let path = base_dir.join(file);
+        let content = fs::read_to_string(&path)
+            .map_err(|error| MdBookError(format!("{}: {}", path.display(), error)))?;
+        let block = mermaid_block(&content, options)?;
+        if before.trim().is_empty() {
+            output.push_str(&indent(&block, before));
+        } else {
+            output.push_str(before);
+            output.push('\n');
+            output.push_str(&block);
+        }
+
+        let after = &rest[start + length + 2..];
+        rest = after.strip_prefix('\n').unwrap_or(after);
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+/// Prefixes every non-empty line of `block` with `indentation`, so a block
+/// replaced inside a list item stays part of it.
+fn indent(block: &str, indentation: &str) -> String {
+    block
+        .split_inclusive('\n')
+        .map(|line| match line.trim().is_empty() {
+            true => line.to_string(),
+            false => format!("{indentation}{line}"),
+        })
+        .collect()
+}
+
+/// Removes up to `width` leading spaces from every line, the indentation of the
+/// fence a block's content sits in.
+fn outdent(content: &str, width: usize) -> String {
+    content
+        .split_inclusive('\n')
+        .map(|line| {
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            &line[spaces.min(width)..]
+        })
+        .collect()
+}
+
+/// Renders a document into a Mermaid code block. `options` lists `workflow=<id>`
+/// (repeatable), `direction=<TD|LR|BT|RL>` and `label=<label>`, separated by spaces.
+fn mermaid_block(content: &str, options: &str) -> Result<String, MdBookError> {
+    let arazzo = crate::parse(content).map_err(|error| MdBookError(error.to_string()))?;
+
+    let mut workflows = Vec::new();
+    let mut builder = MermaidFlowchart::builder();
+    for option in options.split_whitespace() {
+        builder = match option.split_once('=') {
+            Some(("workflow", workflow_id)) => {
+                if !arazzo.workflows.iter().any(|w| w.workflow_id == workflow_id) {
+                    return Err(MdBookError(format!("Workflow not found: {workflow_id}")));
+                }
+                workflows.push(workflow_id.to_string());
+                builder
+            }
+            Some(("direction", direction)) => {
+                builder.direction(direction.parse::<Direction>().map_err(MdBookError)?)
+            }
+            Some(("label", label)) => builder.step_label(StepLabel::from(label.to_string())),
+            _ => return Err(MdBookError(format!("unknown option '{option}'"))),
+        };
+    }
+    if !workflows.is_empty() {
+        builder = builder.workflows(workflows, 0);
+    }
+
+    Ok(format!("```mermaid\n{}```\n", builder.build().render(&arazzo)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preprocess_fenced_block() {
+        let content = concat!(
+            "# Workflows\n",
+            "\n",
+            "```arazzo direction=LR\n",
+            "info:\n",
+            "  title: t\n",
+            "workflows:\n",
+            "  - workflowId: w\n",
+            "    steps:\n",
+            "      - stepId: s\n",
+            "```\n",
+            "\n",
+            "````markdown\n",
+            "{{#arazzo missing.yml}}\n",
+            "````\n",
+        );
+
+        let actual = preprocess_chapter(content, Path::new("fixtures")).unwrap();
+
+        let expected = concat!(
+            "# Workflows\n",
+            "\n",
+            "```mermaid\n",
+            "---\n",
+            "title: t\n",
+            "---\n",
+            "flowchart LR\n",
+            "    subgraph w\n",
+            "    w_s --> wEndNode((End))\n",
+            "    end\n",
+            "```\n",
+            "\n",
+            "````markdown\n",
+            "{{#arazzo missing.yml}}\n",
+            "````\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn preprocess_book() {
+        let input = serde_json::json!([
+            { "root": ".", "config": { "book": { "src": "fixtures" } } },
+            {
+                "sections": [{
+                    "Chapter": {
+                        "name": "Minimal",
+                        "content": "Intro\n{{#arazzo minimal.yml workflow=w}}\nOutro\n",
+                        "path": "minimal.md",
+                        "sub_items": [],
+                    }
+                }]
+            }
+        ]);
+
+        let output = preprocess(&input.to_string()).unwrap();
+        let book: Value = serde_json::from_str(&output).unwrap();
+
+        let actual = book["sections"][0]["Chapter"]["content"].as_str().unwrap();
+
+        let expected = concat!(
+            "Intro\n",
+            "```mermaid\n",
+            "---\n",
+            "title: t\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph w\n",
+            "    w_s --> wEndNode((End))\n",
+            "    end\n",
+            "```\n",
+            "Outro\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn preprocess_directive_after_text() {
+        let content = "See {{#arazzo minimal.yml}} below.\n";
+
+        let actual = preprocess_chapter(content, Path::new("fixtures")).unwrap();
+
+        let expected = concat!(
+            "See \n",
+            "```mermaid\n",
+            "---\n",
+            "title: t\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph w\n",
+            "    w_s --> wEndNode((End))\n",
+            "    end\n",
+            "```\n",
+            " below.\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn preprocess_indented_block() {
+        let content = concat!(
+            "1. Workflow:\n",
+            "\n",
+            "   ```arazzo\n",
+            "   info:\n",
+            "     title: t\n",
+            "   workflows:\n",
+            "     - workflowId: w\n",
+            "       steps:\n",
+            "         - stepId: s\n",
+            "   ```\n",
+            "2. Next\n",
+        );
+
+        let actual = preprocess_chapter(content, Path::new("fixtures")).unwrap();
+
+        let expected = concat!(
+            "1. Workflow:\n",
+            "\n",
+            "   ```mermaid\n",
+            "   ---\n",
+            "   title: t\n",
+            "   ---\n",
+            "   flowchart TD\n",
+            "       subgraph w\n",
+            "       w_s --> wEndNode((End))\n",
+            "       end\n",
+            "   ```\n",
+            "2. Next\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn preprocess_unknown_workflow() {
+        let content = "{{#arazzo minimal.yml workflow=missing}}\n";
+
+        let actual = preprocess_chapter(content, Path::new("fixtures"));
+
+        let expected = Err(MdBookError(String::from("Workflow not found: missing")));
+
+        assert_eq!(expected, actual);
+    }
+}
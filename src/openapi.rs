@@ -0,0 +1,272 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::{fs, io};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::arazzo::{ArazzoDocument, SourceDescriptionType};
+use crate::operation::Operation;
+
+const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenApiDocument {
+    servers: Option<Vec<OpenApiServer>>,
+    paths: Option<IndexMap<String, IndexMap<String, serde_json::Value>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenApiServer {
+    url: String,
+}
+
+/// An operation declared in an OpenAPI source description.
+#[derive(Debug, PartialEq)]
+pub struct OpenApiOperation {
+    pub source: String,
+    /// The URL of the source's first server.
+    pub server: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub response_codes: Vec<String>,
+}
+
+/// Operations of every local OpenAPI source description of an Arazzo document.
+#[derive(Debug, Default)]
+pub struct OpenApiSources {
+    pub sources: Vec<String>,
+    pub operations: Vec<OpenApiOperation>,
+}
+
+#[derive(Debug)]
+pub enum OpenApiError {
+    Io(String, io::Error),
+    Parse(String, yaml_serde::Error),
+}
+
+impl Display for OpenApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenApiError::Io(url, error) => {
+                write!(f, "Failed to read source description {}: {}", url, error)
+            }
+            OpenApiError::Parse(url, error) => {
+                write!(f, "Failed to parse source description {}: {}", url, error)
+            }
+        }
+    }
+}
+
+impl Error for OpenApiError {}
+
+impl OpenApiSources {
+    /// Loads the local OpenAPI source descriptions, resolving URLs relative to `base_dir`.
+    /// Remote URLs are skipped.
+    pub fn load(arazzo: &ArazzoDocument, base_dir: &Path) -> Result<OpenApiSources, OpenApiError> {
+        let mut sources = OpenApiSources::default();
+
+        for source_description in arazzo.source_descriptions.iter().flatten() {
+            if source_description.source_type == Some(SourceDescriptionType::Arazzo)
+                || is_remote(&source_description.url)
+            {
+                continue;
+            }
+
+            let url = &source_description.url;
+            let content = fs::read_to_string(base_dir.join(url))
+                .map_err(|error| OpenApiError::Io(url.clone(), error))?;
+            let document: OpenApiDocument = yaml_serde::from_str(&content)
+                .map_err(|error| OpenApiError::Parse(url.clone(), error))?;
+
+            sources.add(&source_description.name, document);
+        }
+
+        Ok(sources)
+    }
+
+    fn add(&mut self, source: &str, document: OpenApiDocument) {
+        self.sources.push(source.to_string());
+        let server = document.servers.iter().flatten().next().map(|server| server.url.clone());
+
+        for (path, path_item) in document.paths.iter().flatten() {
+            for (method, operation) in path_item {
+                if !METHODS.contains(&method.as_str()) {
+                    continue;
+                }
+
+                self.operations.push(OpenApiOperation {
+                    source: source.to_string(),
+                    server: server.clone(),
+                    method: method.to_uppercase(),
+                    path: path.clone(),
+                    operation_id: string_field(operation, "operationId"),
+                    summary: string_field(operation, "summary"),
+                    response_codes: operation
+                        .get("responses")
+                        .and_then(|v| v.as_object())
+                        .map_or(Vec::new(), |v| v.keys().cloned().collect()),
+                });
+            }
+        }
+    }
+
+    /// Whether references to `operation` can be checked against a loaded source.
+    pub fn covers(&self, operation: &Operation) -> bool {
+        match &operation.source {
+            Some(source) => self.sources.contains(source),
+            None => !self.sources.is_empty(),
+        }
+    }
+
+    pub fn resolve(&self, operation: &Operation) -> Option<&OpenApiOperation> {
+        self.operations.iter().find(|o| {
+            let source_matches = operation.source.as_ref().is_none_or(|s| s == &o.source);
+            let operation_matches = match &operation.operation_id {
+                Some(operation_id) => o.operation_id.as_ref() == Some(operation_id),
+                None => operation.method.as_ref() == Some(&o.method)
+                    && operation.path.as_ref() == Some(&o.path),
+            };
+
+            source_matches && operation_matches
+        })
+    }
+
+    /// Fills in what the OpenAPI description knows about the operation.
+    pub fn enrich(&self, mut operation: Operation) -> Operation {
+        if let Some(resolved) = self.resolve(&operation) {
+            operation.source = Some(resolved.source.clone());
+            operation.method = Some(resolved.method.clone());
+            operation.path = Some(resolved.path.clone());
+            operation.operation_id = operation.operation_id.or(resolved.operation_id.clone());
+            operation.summary = resolved.summary.clone();
+            operation.response_codes = resolved.response_codes.clone();
+        }
+
+        operation
+    }
+
+    /// Lists the steps whose operation is not declared in the loaded sources.
+    pub fn validate(&self, arazzo: &ArazzoDocument) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for workflow in &arazzo.workflows {
+            for step in &workflow.steps {
+                let Some(operation) = Operation::from_step(step) else {
+                    continue;
+                };
+
+                if let Some(source) = &operation.source
+                    && !arazzo.source_descriptions.iter().flatten().any(|s| &s.name == source)
+                {
+                    problems.push(format!(
+                        "{}.{}: unknown source description '{}'",
+                        workflow.workflow_id, step.step_id, source,
+                    ));
+                } else if self.covers(&operation) && self.resolve(&operation).is_none() {
+                    let reference = step.operation_id.as_deref().or(step.operation_path.as_deref());
+                    problems.push(format!(
+                        "{}.{}: operation '{}' not found",
+                        workflow.workflow_id,
+                        step.step_id,
+                        reference.unwrap_or_default(),
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+fn is_remote(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn string_field(value: &serde_json::Value, field: &str) -> Option<String> {
+    value.get(field).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fixture() -> (ArazzoDocument, OpenApiSources) {
+        let content = fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let sources = OpenApiSources::load(&arazzo, Path::new("fixtures")).unwrap();
+
+        (arazzo, sources)
+    }
+
+    #[test]
+    fn load_local_source() {
+        let (_, sources) = load_fixture();
+
+        let actual = sources.operations.first().unwrap();
+
+        let expected = OpenApiOperation {
+            source: String::from("creditCardApi"),
+            server: Some(String::from("https://api.example.com/v1")),
+            method: String::from("POST"),
+            path: String::from("/authorizations"),
+            operation_id: Some(String::from("createAuthorization")),
+            summary: Some(String::from("Create an authorization")),
+            response_codes: vec![String::from("201"), String::from("400")],
+        };
+
+        assert_eq!(3, sources.operations.len());
+        assert_eq!(&expected, actual);
+    }
+
+    #[test]
+    fn enrich_by_operation_path() {
+        let (_, sources) = load_fixture();
+        let operation = Operation {
+            source: Some(String::from("creditCardApi")),
+            method: Some(String::from("POST")),
+            path: Some(String::from("/captures")),
+            ..Operation::default()
+        };
+
+        let actual = sources.enrich(operation);
+
+        assert_eq!(Some(String::from("captureAuthorization")), actual.operation_id);
+        assert_eq!(Some(String::from("Capture an authorization")), actual.summary);
+    }
+
+    #[test]
+    fn validate_fixture() {
+        let (arazzo, sources) = load_fixture();
+
+        let actual = sources.validate(&arazzo);
+
+        assert!(actual.is_empty());
+    }
+
+    #[test]
+    fn validate_missing_operation() {
+        let (mut arazzo, sources) = load_fixture();
+        arazzo.workflows[0].steps[0].operation_id =
+            Some(String::from("$sourceDescriptions.creditCardApi.missing"));
+        arazzo.workflows[0].steps[1].operation_id =
+            Some(String::from("$sourceDescriptions.unknownApi.missing"));
+
+        let actual = sources.validate(&arazzo);
+
+        let expected = vec![
+            String::from(
+                "authorizeThenCapture.createAuthorizationStep: operation '$sourceDescriptions.creditCardApi.missing' not found",
+            ),
+            String::from(
+                "authorizeThenCapture.captureAuthorizationStep: unknown source description 'unknownApi'",
+            ),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+}
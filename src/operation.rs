@@ -0,0 +1,125 @@
+use crate::arazzo::Step;
+
+/// The operation a step calls, as far as it can be told from the step itself.
+#[derive(Debug, Default, PartialEq)]
+pub struct Operation {
+    pub source: Option<String>,
+    pub operation_id: Option<String>,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub summary: Option<String>,
+    pub response_codes: Vec<String>,
+}
+
+impl Operation {
+    pub fn from_step(step: &Step) -> Option<Operation> {
+        if let Some(operation_id) = step.operation_id.as_deref() {
+            Some(from_operation_id(operation_id))
+        } else {
+            step.operation_path.as_deref().map(from_operation_path)
+        }
+    }
+}
+
+/// Parses `$sourceDescriptions.<name>.<operationId>` or a bare `<operationId>`.
+fn from_operation_id(operation_id: &str) -> Operation {
+    match operation_id
+        .strip_prefix("$sourceDescriptions.")
+        .and_then(|v| v.split_once('.'))
+    {
+        Some((source, operation_id)) => Operation {
+            source: Some(source.to_string()),
+            operation_id: Some(operation_id.to_string()),
+            ..Operation::default()
+        },
+        None => Operation {
+            operation_id: Some(operation_id.to_string()),
+            ..Operation::default()
+        },
+    }
+}
+
+/// Parses `{$sourceDescriptions.<name>.url}#/paths/<escaped path>/<method>`.
+fn from_operation_path(operation_path: &str) -> Operation {
+    let (url, pointer) = operation_path
+        .split_once('#')
+        .unwrap_or((operation_path, ""));
+
+    let source = url
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .strip_prefix("$sourceDescriptions.")
+        .and_then(|v| v.strip_suffix(".url"))
+        .map(str::to_string);
+
+    let mut tokens = pointer.strip_prefix("/paths/").unwrap_or_default().split('/');
+    let path = tokens.next().filter(|v| !v.is_empty()).map(unescape_pointer_token);
+    let method = tokens.next().map(str::to_uppercase);
+
+    Operation {
+        source,
+        operation_id: None,
+        method,
+        path,
+        ..Operation::default()
+    }
+}
+
+pub fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_operation_id_with_source() {
+        let actual = from_operation_id("$sourceDescriptions.creditCardApi.createAuthorization");
+
+        let expected = Operation {
+            source: Some(String::from("creditCardApi")),
+            operation_id: Some(String::from("createAuthorization")),
+            method: None,
+            path: None,
+            summary: None,
+            response_codes: Vec::new(),
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn from_operation_id_without_source() {
+        let actual = from_operation_id("createAuthorization");
+
+        let expected = Operation {
+            source: None,
+            operation_id: Some(String::from("createAuthorization")),
+            method: None,
+            path: None,
+            summary: None,
+            response_codes: Vec::new(),
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn from_operation_path_with_source() {
+        let actual = from_operation_path(
+            "{$sourceDescriptions.creditCardApi.url}#/paths/~1authorizations~1{id}/post",
+        );
+
+        let expected = Operation {
+            source: Some(String::from("creditCardApi")),
+            operation_id: None,
+            method: Some(String::from("POST")),
+            path: Some(String::from("/authorizations/{id}")),
+            summary: None,
+            response_codes: Vec::new(),
+        };
+
+        assert_eq!(expected, actual);
+    }
+}
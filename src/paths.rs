@@ -0,0 +1,467 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::arazzo::{Action, Transition, Workflow};
+use crate::renderer::{describe_criteria, should_branch};
+
+/// Every distinct start-to-end path through a workflow.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowPaths {
+    pub workflow_id: String,
+    pub paths: Vec<ExecutionPath>,
+}
+
+/// The steps a path runs, and how it ends: `end`, `failed`, `goto workflow <id>`,
+/// `retry limit`, `loop limit` or `unknown step <id>`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPath {
+    pub steps: Vec<PathStep>,
+    pub end: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PathStep {
+    pub step_id: String,
+    pub outcomes: Vec<Outcome>,
+}
+
+/// A decision taken along the path: the step's success criteria when `action` is
+/// `None`, otherwise the criteria of the named action.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Outcome {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub criteria: Option<String>,
+    pub outcome: bool,
+}
+
+#[derive(Clone, Default)]
+struct Trail {
+    steps: Vec<PathStep>,
+    visits: HashMap<usize, usize>,
+    /// Retries taken so far, by step index and action name.
+    retries: HashMap<(usize, String), u32>,
+}
+
+impl Trail {
+    fn decide(&self, action: Option<&Action>, criteria: Option<String>, outcome: bool) -> Trail {
+        let mut trail = self.clone();
+        if let Some(step) = trail.steps.last_mut() {
+            step.outcomes.push(Outcome {
+                action: action.map(|action| action.name.clone()),
+                criteria: criteria.filter(|criteria| !criteria.is_empty()),
+                outcome,
+            });
+        }
+
+        trail
+    }
+}
+
+/// Walks the workflow the way the flowchart draws it. Each step is entered at
+/// most `max_visits` times per path, so loops and retries stay bounded.
+pub fn enumerate(workflow: &Workflow, max_visits: usize) -> WorkflowPaths {
+    let mut walker = Walker {
+        workflow,
+        max_visits,
+        paths: Vec::new(),
+    };
+    if !workflow.steps.is_empty() {
+        walker.visit(0, Trail::default());
+    }
+
+    WorkflowPaths {
+        workflow_id: workflow.workflow_id.clone(),
+        paths: walker.paths,
+    }
+}
+
+struct Walker<'a> {
+    workflow: &'a Workflow,
+    max_visits: usize,
+    paths: Vec<ExecutionPath>,
+}
+
+impl Walker<'_> {
+    fn finish(&mut self, trail: Trail, end: &str) {
+        self.paths.push(ExecutionPath {
+            steps: trail.steps,
+            end: end.to_string(),
+        });
+    }
+
+    fn visit(&mut self, index: usize, mut trail: Trail) {
+        let visits = trail.visits.entry(index).or_default();
+        if *visits >= self.max_visits {
+            return self.finish(trail, "loop limit");
+        }
+        *visits += 1;
+
+        let step = &self.workflow.steps[index];
+        trail.steps.push(PathStep {
+            step_id: step.step_id.clone(),
+            outcomes: Vec::new(),
+        });
+
+        if !should_branch(step) {
+            return self.take(index, true, None, trail);
+        }
+
+        let criteria = step.success_criteria.as_deref().map(describe_criteria);
+        let succeeded = trail.decide(None, criteria.clone(), true);
+        self.follow(index, step.on_success.as_deref(), true, succeeded);
+        let failed = trail.decide(None, criteria, false);
+        self.follow(index, step.on_failure.as_deref(), false, failed);
+    }
+
+    /// Tries the actions in order. An action with criteria branches: when they hold
+    /// the action is taken, otherwise the next action is tried. When no action is
+    /// taken, a success moves on to the next step and a failure fails the workflow.
+    fn follow(&mut self, index: usize, actions: Option<&[Action]>, success: bool, trail: Trail) {
+        let mut trail = trail;
+        for action in actions.into_iter().flatten() {
+            let Some(criteria) = action.criteria.as_deref() else {
+                return self.take(index, success, Some(action), trail);
+            };
+            let criteria = Some(describe_criteria(criteria));
+            let taken = trail.decide(Some(action), criteria.clone(), true);
+            self.take(index, success, Some(action), taken);
+            trail = trail.decide(Some(action), criteria, false);
+        }
+
+        self.take(index, success, None, trail);
+    }
+
+    /// Carries on the way a run would: see [`Workflow::transition`]. A retry through
+    /// another step lists that step before entering the retried one again.
+    fn take(&mut self, index: usize, success: bool, action: Option<&Action>, mut trail: Trail) {
+        let key = (index, action.map_or(String::new(), |action| action.name.clone()));
+        let retries = trail.retries.get(&key).copied().unwrap_or_default();
+
+        match self.workflow.transition(index, success, action, retries) {
+            Transition::Step(target) => self.visit(target, trail),
+            Transition::Workflow(workflow_id) => {
+                self.finish(trail, &format!("goto workflow {workflow_id}"))
+            }
+            Transition::Retry { step, .. } => {
+                *trail.retries.entry(key).or_default() += 1;
+                if let Some(step) = step {
+                    trail.steps.push(PathStep {
+                        step_id: self.workflow.steps[step].step_id.clone(),
+                        outcomes: Vec::new(),
+                    });
+                }
+                self.visit(index, trail)
+            }
+            Transition::End => self.finish(trail, "end"),
+            Transition::Failed => self.finish(trail, "failed"),
+            Transition::RetryLimit => self.finish(trail, "retry limit"),
+            Transition::UnknownStep(step_id) => {
+                self.finish(trail, &format!("unknown step {step_id}"))
+            }
+        }
+    }
+}
+
+/// One table per workflow, one row per path.
+pub fn to_markdown(title: &str, workflows: &[WorkflowPaths]) -> String {
+    let mut output = format!("# {title}\n");
+
+    for workflow in workflows {
+        output.push_str(&format!("\n## {}\n\n", workflow.workflow_id));
+        output.push_str("| # | Steps | Outcomes | End |\n");
+        output.push_str("|---|-------|----------|-----|\n");
+
+        for (i, path) in workflow.paths.iter().enumerate() {
+            let steps = path
+                .steps
+                .iter()
+                .map(|step| format!("`{}`", step.step_id))
+                .collect::<Vec<String>>()
+                .join(" → ");
+            let outcomes = path
+                .steps
+                .iter()
+                .flat_map(|step| step.outcomes.iter().map(move |o| describe_outcome(step, o)))
+                .collect::<Vec<String>>()
+                .join("<br>");
+
+            output.push_str(&format!("| {} | {} | {} | {} |\n", i + 1, steps, outcomes, path.end));
+        }
+    }
+
+    output
+}
+
+fn describe_outcome(step: &PathStep, outcome: &Outcome) -> String {
+    let subject = match &outcome.action {
+        Some(action) => format!("{}.{}", step.step_id, action),
+        None => step.step_id.clone(),
+    };
+    let criteria = match &outcome.criteria {
+        Some(criteria) => format!(" `{}`", criteria.replace('|', "\\|")),
+        None => String::new(),
+    };
+
+    format!("{subject}{criteria}: {}", outcome.outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::ArazzoDocument;
+
+    fn outcome(criteria: &str, outcome: bool) -> Outcome {
+        Outcome {
+            action: None,
+            criteria: Some(String::from(criteria)),
+            outcome,
+        }
+    }
+
+    fn step(step_id: &str, outcomes: Vec<Outcome>) -> PathStep {
+        PathStep {
+            step_id: String::from(step_id),
+            outcomes,
+        }
+    }
+
+    #[test]
+    fn enumerate_fixture() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+
+        let actual = enumerate(&arazzo.workflows[0], 2);
+
+        let ok = |step_id| step(step_id, vec![outcome("$statusCode == 201", true)]);
+        let ng = |step_id| step(step_id, vec![outcome("$statusCode == 201", false)]);
+        let path = |steps, end: &str| ExecutionPath {
+            steps,
+            end: String::from(end),
+        };
+        let expected = WorkflowPaths {
+            workflow_id: String::from("authorizeThenCapture"),
+            paths: vec![
+                path(vec![ok("createAuthorizationStep"), ok("captureAuthorizationStep")], "end"),
+                path(
+                    vec![
+                        ok("createAuthorizationStep"),
+                        ng("captureAuthorizationStep"),
+                        ok("reverseAuthorizationStep"),
+                    ],
+                    "end",
+                ),
+                path(
+                    vec![
+                        ok("createAuthorizationStep"),
+                        ng("captureAuthorizationStep"),
+                        ng("reverseAuthorizationStep"),
+                    ],
+                    "end",
+                ),
+                path(vec![ng("createAuthorizationStep"), ok("reverseAuthorizationStep")], "end"),
+                path(vec![ng("createAuthorizationStep"), ng("reverseAuthorizationStep")], "end"),
+            ],
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn enumerate_bounded_retry() {
+        let content = concat!(
+            "workflowId: workflowFoo\n",
+            "steps:\n",
+            "  - stepId: stepFoo\n",
+            "    successCriteria:\n",
+            "      - condition: $statusCode == 200\n",
+            "    onFailure:\n",
+            "      - name: retryFoo\n",
+            "        type: retry\n",
+            "        retryLimit: 1\n",
+            "        criteria:\n",
+            "          - condition: $statusCode == 503\n",
+        );
+        let workflow: Workflow = yaml_serde::from_str(content).unwrap();
+
+        let paths = enumerate(&workflow, 3).paths;
+
+        let actual = paths
+            .iter()
+            .map(|path| (path.steps.len(), path.end.as_str()))
+            .collect::<Vec<(usize, &str)>>();
+
+        let expected = vec![
+            (1, "end"),
+            (2, "end"),
+            (2, "retry limit"),
+            (2, "failed"),
+            (1, "failed"),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    /// The step IDs and end of each path.
+    fn walk(content: &str, max_visits: usize) -> Vec<(Vec<String>, String)> {
+        let workflow: Workflow = yaml_serde::from_str(content).unwrap();
+
+        enumerate(&workflow, max_visits)
+            .paths
+            .into_iter()
+            .map(|path| {
+                let steps = path.steps.into_iter().map(|step| step.step_id).collect();
+                (steps, path.end)
+            })
+            .collect()
+    }
+
+    fn path(steps: &[&str], end: &str) -> (Vec<String>, String) {
+        (steps.iter().map(|step| step.to_string()).collect(), String::from(end))
+    }
+
+    #[test]
+    fn enumerate_rejected_on_success_moves_on() {
+        let content = concat!(
+            "workflowId: workflowFoo\n",
+            "steps:\n",
+            "  - stepId: stepFoo\n",
+            "    successCriteria:\n",
+            "      - condition: $statusCode == 200\n",
+            "    onSuccess:\n",
+            "      - name: endFoo\n",
+            "        type: end\n",
+            "        criteria:\n",
+            "          - condition: $response.body#/done == true\n",
+            "  - stepId: stepBar\n",
+        );
+
+        let actual = walk(content, 2);
+
+        let expected = vec![
+            path(&["stepFoo"], "end"),
+            path(&["stepFoo", "stepBar"], "end"),
+            path(&["stepFoo"], "failed"),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn enumerate_retry_once_without_limit() {
+        let content = concat!(
+            "workflowId: workflowFoo\n",
+            "steps:\n",
+            "  - stepId: stepFoo\n",
+            "    successCriteria:\n",
+            "      - condition: $statusCode == 200\n",
+            "    onFailure:\n",
+            "      - name: retryFoo\n",
+            "        type: retry\n",
+        );
+
+        let actual = walk(content, 5);
+
+        let expected = vec![
+            path(&["stepFoo"], "end"),
+            path(&["stepFoo", "stepFoo"], "end"),
+            path(&["stepFoo", "stepFoo"], "retry limit"),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn enumerate_retry_through_workflow_and_step() {
+        let content = |target: &str| {
+            format!(
+                concat!(
+                    "workflowId: workflowFoo\n",
+                    "steps:\n",
+                    "  - stepId: stepLogin\n",
+                    "  - stepId: stepFoo\n",
+                    "    successCriteria:\n",
+                    "      - condition: $statusCode == 200\n",
+                    "    onFailure:\n",
+                    "      - name: retryFoo\n",
+                    "        type: retry\n",
+                    "        {}\n",
+                ),
+                target,
+            )
+        };
+
+        let actual = [
+            walk(&content("workflowId: refreshWorkflow"), 2),
+            walk(&content("stepId: stepLogin"), 2),
+        ];
+
+        let expected = [
+            vec![
+                path(&["stepLogin", "stepFoo"], "end"),
+                path(&["stepLogin", "stepFoo", "stepFoo"], "end"),
+                path(&["stepLogin", "stepFoo", "stepFoo"], "retry limit"),
+            ],
+            vec![
+                path(&["stepLogin", "stepFoo"], "end"),
+                path(&["stepLogin", "stepFoo", "stepLogin", "stepFoo"], "end"),
+                path(&["stepLogin", "stepFoo", "stepLogin", "stepFoo"], "retry limit"),
+            ],
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn enumerate_goto_unknown_step() {
+        let content = concat!(
+            "workflowId: workflowFoo\n",
+            "steps:\n",
+            "  - stepId: stepFoo\n",
+            "    successCriteria:\n",
+            "      - condition: $statusCode == 200\n",
+            "    onFailure:\n",
+            "      - name: gotoBar\n",
+            "        type: goto\n",
+            "        stepId: stepBar\n",
+        );
+
+        let actual = walk(content, 2);
+
+        let expected = vec![path(&["stepFoo"], "end"), path(&["stepFoo"], "unknown step stepBar")];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn markdown_table() {
+        let paths = vec![WorkflowPaths {
+            workflow_id: String::from("workflowFoo"),
+            paths: vec![ExecutionPath {
+                steps: vec![step("stepFoo", vec![outcome("$statusCode == 200", false)])],
+                end: String::from("failed"),
+            }],
+        }];
+
+        let actual = to_markdown("Workflows", &paths);
+
+        let expected = concat!(
+            "# Workflows\n",
+            "\n",
+            "## workflowFoo\n",
+            "\n",
+            "| # | Steps | Outcomes | End |\n",
+            "|---|-------|----------|-----|\n",
+            "| 1 | `stepFoo` | stepFoo `$statusCode == 200`: false | failed |\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+}
@@ -1,134 +1,814 @@
-use crate::arazzo::{Action, ActionType, ArazzoDocument, Criteria, Step};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::arazzo::{
+    Action, ActionType, ArazzoDocument, Criteria, CriteriaType, CriteriaTypeName, Step, Workflow,
+};
+use crate::composition::{ArazzoSources, parse_workflow_reference};
+use crate::condition::Condition;
+use crate::expression::criteria_type_name;
+use crate::openapi::OpenApiSources;
+use crate::operation::Operation;
+use crate::renderer::coverage::{
+    Coverage, Hits, StepCoverage, coverage_styles, coverage_summary,
+};
+use crate::renderer::diff::{Diff, diff_styles};
+use crate::renderer::trace::{Trace, TraceOutcome, trace_styles};
+
+pub mod coverage;
+pub mod dataflow;
+pub mod diff;
+pub mod html;
+pub mod markdown;
+pub mod trace;
 
 pub trait Renderer {
     fn render(&self, document: &ArazzoDocument) -> String;
 }
 
-pub struct MermaidFlowchart;
+/// Renders Arazzo workflows as a Mermaid flowchart. Built with [`MermaidFlowchart::builder`].
+pub struct MermaidFlowchart {
+    workflow_filter: Option<WorkflowFilter>,
+    options: FlowchartOptions,
+    sources: OpenApiSources,
+    external_workflows: ArazzoSources,
+    trace: Option<Trace>,
+    coverage: Option<Coverage>,
+    diff: Option<Diff>,
+}
+
+/// Layout and styling of the flowchart. Every field can be set from a config file.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FlowchartOptions {
+    pub direction: Direction,
+    pub step_label: StepLabel,
+    pub theme: Option<String>,
+    pub theme_variables: Option<IndexMap<String, String>>,
+    pub class_defs: Option<ClassDefs>,
+    pub expand_external: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub enum Direction {
+    #[default]
+    TD,
+    LR,
+    BT,
+    RL,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::TD => write!(f, "TD"),
+            Direction::LR => write!(f, "LR"),
+            Direction::BT => write!(f, "BT"),
+            Direction::RL => write!(f, "RL"),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "TD" | "TB" => Ok(Direction::TD),
+            "LR" => Ok(Direction::LR),
+            "BT" => Ok(Direction::BT),
+            "RL" => Ok(Direction::RL),
+            _ => Err(format!("invalid direction '{s}', expected TD, LR, BT or RL")),
+        }
+    }
+}
+
+/// What step nodes show: the description, the called operation, or a template
+/// mixing `{stepId}`, `{description}`, `{operationId}`, `{method}`, `{path}`,
+/// `{source}`, `{operation}`, `{summary}` and `{responses}`.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(from = "String")]
+#[non_exhaustive]
+pub enum StepLabel {
+    #[default]
+    Description,
+    Operation,
+    Template(String),
+}
+
+impl From<String> for StepLabel {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "description" => StepLabel::Description,
+            "operation" => StepLabel::Operation,
+            _ => StepLabel::Template(value),
+        }
+    }
+}
+
+impl FromStr for StepLabel {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(StepLabel::from(s.to_string()))
+    }
+}
+
+/// Mermaid `classDef` styles applied to each kind of node.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ClassDefs {
+    pub step: String,
+    pub decision: String,
+    pub end: String,
+    pub cross_workflow: String,
+    pub retry: String,
+}
+
+impl Default for ClassDefs {
+    fn default() -> Self {
+        ClassDefs {
+            step: String::from("fill:#e8f0fe,stroke:#1a73e8"),
+            decision: String::from("fill:#fef7e0,stroke:#f9ab00"),
+            end: String::from("fill:#e6f4ea,stroke:#1e8e3e"),
+            cross_workflow: String::from("fill:#f3e8fd,stroke:#9334e6,stroke-dasharray:4 2"),
+            retry: String::from("fill:#fce8e6,stroke:#d93025"),
+        }
+    }
+}
+
+/// Restricts rendering to the given workflows, plus the workflows they reach
+/// through `goto` actions or `dependsOn` within `depth` hops.
+struct WorkflowFilter {
+    workflow_ids: Vec<String>,
+    depth: usize,
+}
+
+/// Builds a [`MermaidFlowchart`] one option at a time, starting from the defaults.
+pub struct MermaidFlowchartBuilder {
+    flowchart: MermaidFlowchart,
+}
+
+impl Default for MermaidFlowchartBuilder {
+    fn default() -> Self {
+        MermaidFlowchartBuilder {
+            flowchart: MermaidFlowchart {
+                workflow_filter: None,
+                options: FlowchartOptions::default(),
+                sources: OpenApiSources::default(),
+                external_workflows: ArazzoSources::default(),
+                trace: None,
+                coverage: None,
+                diff: None,
+            },
+        }
+    }
+}
+
+impl MermaidFlowchartBuilder {
+    /// Renders only the given workflows and those they reach within `depth` hops.
+    pub fn workflows<I, S>(mut self, workflow_ids: I, depth: usize) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.flowchart.workflow_filter = Some(WorkflowFilter {
+            workflow_ids: workflow_ids.into_iter().map(Into::into).collect(),
+            depth,
+        });
+        self
+    }
+
+    /// Replaces every layout and styling option, as loaded from a config file.
+    pub fn options(mut self, options: FlowchartOptions) -> Self {
+        self.flowchart.options = options;
+        self
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.flowchart.options.direction = direction;
+        self
+    }
+
+    pub fn step_label(mut self, step_label: StepLabel) -> Self {
+        self.flowchart.options.step_label = step_label;
+        self
+    }
+
+    pub fn theme(mut self, theme: impl Into<String>) -> Self {
+        self.flowchart.options.theme = Some(theme.into());
+        self
+    }
+
+    pub fn theme_variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let theme_variables = self.flowchart.options.theme_variables.get_or_insert_default();
+        theme_variables.insert(name.into(), value.into());
+        self
+    }
+
+    /// Applies the default `classDef` styles unless others were given.
+    pub fn styled(mut self) -> Self {
+        self.flowchart.options.class_defs.get_or_insert_default();
+        self
+    }
+
+    pub fn class_defs(mut self, class_defs: ClassDefs) -> Self {
+        self.flowchart.options.class_defs = Some(class_defs);
+        self
+    }
+
+    /// Resolves operations against the given OpenAPI source descriptions.
+    pub fn sources(mut self, sources: OpenApiSources) -> Self {
+        self.flowchart.sources = sources;
+        self
+    }
+
+    /// Composes the given Arazzo documents, drawn as subgraphs when `expand` is set
+    /// and as collapsed nodes otherwise.
+    pub fn external_workflows(mut self, external_workflows: ArazzoSources, expand: bool) -> Self {
+        self.flowchart.external_workflows = external_workflows;
+        self.flowchart.options.expand_external = expand;
+        self
+    }
+
+    pub fn trace(mut self, trace: Trace) -> Self {
+        self.flowchart.trace = Some(trace);
+        self
+    }
+
+    pub fn coverage(mut self, coverage: Coverage) -> Self {
+        self.flowchart.coverage = Some(coverage);
+        self
+    }
+
+    pub fn diff(mut self, diff: Diff) -> Self {
+        self.flowchart.diff = Some(diff);
+        self
+    }
+
+    pub fn build(self) -> MermaidFlowchart {
+        self.flowchart
+    }
+}
+
+impl MermaidFlowchart {
+    pub fn builder() -> MermaidFlowchartBuilder {
+        MermaidFlowchartBuilder::default()
+    }
+
+    pub fn selected_workflows<'a>(&self, arazzo: &'a ArazzoDocument) -> Vec<&'a Workflow> {
+        select_workflows(arazzo, self.workflow_filter.as_ref())
+    }
+
+    /// Renders the workflows selected by `filter` instead of the flowchart's own
+    /// filter, keeping every other option.
+    fn render_filtered(&self, arazzo: &ArazzoDocument, filter: &WorkflowFilter) -> String {
+        let workflows = select_workflows(arazzo, Some(filter));
+        let (output, _) = flowchart(&arazzo.info.title, arazzo, &workflows, self, None);
+
+        output
+    }
+
+    /// Renders each selected workflow as its own Mermaid document. Calls to other
+    /// workflows become stub nodes linking to the URL returned by `link`.
+    pub fn render_split<'a>(
+        &self,
+        arazzo: &'a ArazzoDocument,
+        link: &dyn Fn(&str) -> String,
+    ) -> Vec<(&'a str, String)> {
+        self.selected_workflows(arazzo)
+            .into_iter()
+            .map(|workflow| {
+                let graph_title = format!("{} - {}", arazzo.info.title, workflow.workflow_id);
+                let (mermaid, _) =
+                    flowchart(&graph_title, arazzo, &[workflow], self, Some(link));
+
+                (workflow.workflow_id.as_str(), mermaid)
+            })
+            .collect()
+    }
+
+    /// Renders the flowchart along with how many branches of each workflow the
+    /// coverage data took, listing those never taken. The summary is empty
+    /// without coverage.
+    pub fn render_with_coverage_summary(&self, arazzo: &ArazzoDocument) -> (String, String) {
+        let workflows = self.selected_workflows(arazzo);
+
+        flowchart(&arazzo.info.title, arazzo, &workflows, self, None)
+    }
+}
 
 impl Renderer for MermaidFlowchart {
     fn render(&self, arazzo: &ArazzoDocument) -> String {
-        let mut output = title(arazzo.info.title.as_ref());
-        output.push_str("flowchart TD\n");
+        let (output, _) = self.render_with_coverage_summary(arazzo);
+
+        output
+    }
+}
+
+/// A rendered subgraph: its node name, its label and the workflow it shows.
+type Graph<'a> = (String, Option<&'a str>, &'a Workflow);
+
+/// Renders the flowchart, along with the coverage summary when coverage is given.
+fn flowchart(
+    graph_title: &str,
+    arazzo: &ArazzoDocument,
+    workflows: &[&Workflow],
+    flowchart: &MermaidFlowchart,
+    link: Option<&dyn Fn(&str) -> String>,
+) -> (String, String) {
+    let options = &flowchart.options;
+    let mut output = title(graph_title);
+    output.push_str(&init(options));
+    output.push_str(&format!("flowchart {}\n", options.direction));
+
+    let mut graphs: Vec<Graph> = workflows
+        .iter()
+        .map(|w| (w.workflow_id.clone(), w.description.as_deref(), *w))
+        .collect();
+    if options.expand_external {
+        for workflow in workflows {
+            for referenced in called_workflows(workflow) {
+                let Some(external) = flowchart.external_workflows.find_workflow(referenced) else {
+                    continue;
+                };
+                let graph_id = workflow_node_name(referenced);
+                if !graphs.iter().any(|(id, _, _)| id == &graph_id) {
+                    graphs.push((graph_id, external.description.as_deref(), external));
+                }
+            }
+        }
+    }
+
+    let mut edges = Edges::default();
+    for (graph_id, graph_label, workflow) in &graphs {
+        let rendered =
+            render_workflow(graph_id, *graph_label, workflow, arazzo, flowchart, &mut edges);
+        output.push_str(&rendered);
+    }
+
+    let mut collapsed: Vec<(String, &str)> = Vec::new();
+    for workflow in workflows {
+        for referenced in called_workflows(workflow) {
+            let node_name = workflow_node_name(referenced);
+            let is_rendered = graphs.iter().any(|(id, _, _)| id == &node_name);
+            let is_known = parse_workflow_reference(referenced).is_some()
+                || arazzo.workflows.iter().any(|w| w.workflow_id == referenced);
+            if !is_rendered && is_known && !collapsed.iter().any(|(n, _)| n == &node_name) {
+                collapsed.push((node_name, referenced));
+            }
+        }
+    }
+    for (node_name, referenced) in &collapsed {
+        output.push_str(&format!(
+            "    {collapsed_node}\n",
+            collapsed_node = CollapsedNode {
+                node_name,
+                node_label: &workflow_node_label(referenced),
+            }
+            .to_mermaid(),
+        ));
+        let url = match parse_workflow_reference(referenced) {
+            Some((source, _)) => {
+                let source = flowchart.external_workflows.find_source(source);
+                source.map(|s| s.url.clone())
+            }
+            None => link.map(|link| link(referenced)),
+        };
+        if let Some(url) = url {
+            output.push_str(&format!("    click {node_name} href \"{url}\"\n"));
+        }
+    }
+
+    if let Some(class_defs) = &options.class_defs {
+        let classes = node_classes(&graphs, &output);
+        output.push_str(&class_definitions(class_defs));
+        output.push_str(&class_assignments(&classes));
+    }
+
+    if let Some(trace) = &flowchart.trace {
+        output.push_str(&trace_styles(trace, &graphs, &edges.edges));
+    }
+
+    let mut summary = String::new();
+    if let Some(coverage) = &flowchart.coverage {
+        output.push_str(&coverage_styles(coverage, &graphs, &edges.edges));
+        summary = coverage_summary(&graphs, &edges.edges);
+    }
+
+    if let Some(diff) = &flowchart.diff {
+        output.push_str(&diff_styles(diff, &graphs));
+    }
+
+    (output, summary)
+}
+
+fn render_workflow(
+    graph_id: &str,
+    graph_label: Option<&str>,
+    workflow: &Workflow,
+    arazzo: &ArazzoDocument,
+    flowchart: &MermaidFlowchart,
+    edges: &mut Edges,
+) -> String {
+    let mut output = String::new();
 
-        for workflow in &arazzo.workflows {
-            output.push_str(&subgraph(
-                &workflow.workflow_id,
-                workflow.description.as_deref(),
+    output.push_str(&subgraph(graph_id, graph_label));
+    edges.graph_id = Some(graph_id.to_string());
+
+    for (i, current_step) in workflow.steps.iter().enumerate() {
+        let coverage = flowchart
+            .coverage
+            .as_ref()
+            .map(|coverage| coverage.step(&workflow.workflow_id, &current_step.step_id));
+        let outcome_hits = |outcome: TraceOutcome| {
+            coverage.as_ref().map(|c| Hits {
+                taken: c.outcome(outcome),
+                reached: c.hits,
+            })
+        };
+
+        if should_branch(current_step) {
+            output.push_str(&to_rhombus_from_rectangle(
+                &RectangleNode {
+                    node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                    node_label: step_label(current_step, flowchart).as_deref(),
+                    subroutine: current_step.workflow_id.is_some(),
+                },
+                &RhombusNode {
+                    node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                    criteria: current_step.success_criteria.as_deref(),
+                },
+                edges,
             ));
 
-            for (i, current_step) in workflow.steps.iter().enumerate() {
-                if should_branch(current_step) {
-                    output.push_str(&to_rhombus_from_rectangle(
-                        &RectangleNode {
-                            node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                            node_label: current_step.description.as_deref(),
-                        },
-                        &RhombusNode {
-                            node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                            criteria: current_step.success_criteria.as_deref(),
-                        },
-                    ));
+            if let Some(on_success) = current_step.on_success.as_deref() {
+                output.push_str(&render_actions(
+                    &current_step.step_id,
+                    &current_step.success_criteria,
+                    on_success,
+                    ActionSide::OnSuccess,
+                    graph_id,
+                    coverage.as_ref(),
+                    edges,
+                ));
+            } else if let Some(next_step) = &workflow.steps.get(i + 1) {
+                output.push_str(&to_rectangle_from_rhombus(
+                    &RhombusNode {
+                        node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                        criteria: current_step.success_criteria.as_deref(),
+                    },
+                    &RectangleNode {
+                        node_name: format!("{}_{}", graph_id, next_step.step_id).as_ref(),
+                        node_label: step_label(next_step, flowchart).as_deref(),
+                        subroutine: next_step.workflow_id.is_some(),
+                    },
+                    Branch {
+                        verdict: Verdict::Ok,
+                        hits: outcome_hits(TraceOutcome::Success),
+                    },
+                    edges,
+                ));
+            } else {
+                output.push_str(&to_end_from_rhombus(
+                    &RhombusNode {
+                        node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                        criteria: current_step.success_criteria.as_deref(),
+                    },
+                    &EndNode {
+                        node_name: graph_id,
+                    },
+                    Branch {
+                        verdict: Verdict::Ok,
+                        hits: outcome_hits(TraceOutcome::Success),
+                    },
+                    edges,
+                ));
+            }
 
-                    if let Some(on_success) = current_step.on_success.as_deref() {
-                        output.push_str(&render_actions(
-                            &current_step.step_id,
-                            &current_step.success_criteria,
-                            on_success,
-                            ActionSide::OnSuccess,
-                            &workflow.workflow_id,
-                        ));
-                    } else if let Some(next_step) = &workflow.steps.get(i + 1) {
-                        output.push_str(&to_rectangle_from_rhombus(
-                            &RhombusNode {
-                                node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                                criteria: current_step.success_criteria.as_deref(),
-                            },
-                            &RectangleNode {
-                                node_name: format!("{}_{}", workflow.workflow_id, next_step.step_id).as_ref(),
-                                node_label: next_step.description.as_deref(),
-                            },
-                            Verdict::Ok,
-                        ));
-                    } else {
-                        output.push_str(&to_end_from_rhombus(
-                            &RhombusNode {
-                                node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                                criteria: current_step.success_criteria.as_deref(),
-                            },
-                            &EndNode {
-                                node_name: &workflow.workflow_id,
-                            },
-                            Verdict::Ok,
-                        ));
-                    }
-
-                    if let Some(on_failure) = current_step.on_failure.as_deref() {
-                        output.push_str(&render_actions(
-                            &current_step.step_id,
-                            &current_step.success_criteria,
-                            on_failure,
-                            ActionSide::OnFailure,
-                            &workflow.workflow_id,
-                        ));
-                    } else {
-                        output.push_str(&to_end_from_rhombus(
-                            &RhombusNode {
-                                node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                                criteria: current_step.success_criteria.as_deref(),
-                            },
-                            &EndNode {
-                                node_name: &workflow.workflow_id,
-                            },
-                            Verdict::Ng,
-                        ));
-                    }
-                } else if let Some(next_step) = &workflow.steps.get(i + 1) {
-                    output.push_str(&to_rectangle_from_rectangle(
-                        &RectangleNode {
-                            node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                            node_label: current_step.description.as_deref(),
-                        },
-                        &RectangleNode {
-                            node_name: format!("{}_{}", workflow.workflow_id, next_step.step_id).as_ref(),
-                            node_label: next_step.description.as_deref(),
-                        },
-                    ));
-                } else {
-                    output.push_str(&to_end_from_rectangle(
-                        &RectangleNode {
-                            node_name: format!("{}_{}", workflow.workflow_id, current_step.step_id).as_ref(),
-                            node_label: current_step.description.as_deref(),
-                        },
-                        &EndNode {
-                            node_name: &workflow.workflow_id,
-                        },
-                    ));
+            if let Some(on_failure) = current_step.on_failure.as_deref() {
+                output.push_str(&render_actions(
+                    &current_step.step_id,
+                    &current_step.success_criteria,
+                    on_failure,
+                    ActionSide::OnFailure,
+                    graph_id,
+                    coverage.as_ref(),
+                    edges,
+                ));
+            } else {
+                output.push_str(&to_end_from_rhombus(
+                    &RhombusNode {
+                        node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                        criteria: current_step.success_criteria.as_deref(),
+                    },
+                    &EndNode {
+                        node_name: graph_id,
+                    },
+                    Branch {
+                        verdict: Verdict::Ng,
+                        hits: outcome_hits(TraceOutcome::Failure),
+                    },
+                    edges,
+                ));
+            }
+        } else if let Some(next_step) = &workflow.steps.get(i + 1) {
+            output.push_str(&to_rectangle_from_rectangle(
+                &RectangleNode {
+                    node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                    node_label: step_label(current_step, flowchart).as_deref(),
+                    subroutine: current_step.workflow_id.is_some(),
+                },
+                &RectangleNode {
+                    node_name: format!("{}_{}", graph_id, next_step.step_id).as_ref(),
+                    node_label: step_label(next_step, flowchart).as_deref(),
+                    subroutine: next_step.workflow_id.is_some(),
+                },
+                edges,
+            ));
+        } else {
+            output.push_str(&to_end_from_rectangle(
+                &RectangleNode {
+                    node_name: format!("{}_{}", graph_id, current_step.step_id).as_ref(),
+                    node_label: step_label(current_step, flowchart).as_deref(),
+                    subroutine: current_step.workflow_id.is_some(),
+                },
+                &EndNode {
+                    node_name: graph_id,
+                },
+                edges,
+            ));
+        }
+    }
+
+    output.push_str("    end\n");
+    edges.graph_id = None;
+
+    for step in &workflow.steps {
+        if let Some(step_workflow_id) = step.workflow_id.as_deref() {
+            let parameters = step
+                .parameters
+                .iter()
+                .flatten()
+                .map(|p| match arazzo.resolve_parameter(p) {
+                    Some(p) => format!("{} = {}", p.name, describe_value(&p.value)),
+                    None => p.name().to_string(),
+                })
+                .collect::<Vec<String>>();
+            output.push_str(&to_workflow_from_subroutine(
+                format!("{}_{}", graph_id, step.step_id).as_ref(),
+                &workflow_node_name(step_workflow_id),
+                &parameters,
+                edges,
+            ));
+        }
+    }
+
+    output
+}
+
+fn init(options: &FlowchartOptions) -> String {
+    let mut config = serde_json::Map::new();
+    if let Some(theme) = &options.theme {
+        config.insert(String::from("theme"), serde_json::json!(theme));
+    }
+    if let Some(theme_variables) = &options.theme_variables {
+        config.insert(String::from("themeVariables"), serde_json::json!(theme_variables));
+    }
+
+    if config.is_empty() {
+        String::new()
+    } else {
+        format!("%%{{init: {}}}%%\n", serde_json::Value::Object(config))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum NodeClass {
+    Step,
+    Decision,
+    End,
+    CrossWorkflow,
+    Retry,
+}
+
+impl NodeClass {
+    const ALL: [NodeClass; 5] = [
+        NodeClass::Step,
+        NodeClass::Decision,
+        NodeClass::End,
+        NodeClass::CrossWorkflow,
+        NodeClass::Retry,
+    ];
+
+    fn class_name(&self) -> &'static str {
+        match self {
+            NodeClass::Step => "stepNode",
+            NodeClass::Decision => "decisionNode",
+            NodeClass::End => "endNode",
+            NodeClass::CrossWorkflow => "crossWorkflowNode",
+            NodeClass::Retry => "retryNode",
+        }
+    }
+
+    fn style<'a>(&self, class_defs: &'a ClassDefs) -> &'a str {
+        match self {
+            NodeClass::Step => &class_defs.step,
+            NodeClass::Decision => &class_defs.decision,
+            NodeClass::End => &class_defs.end,
+            NodeClass::CrossWorkflow => &class_defs.cross_workflow,
+            NodeClass::Retry => &class_defs.retry,
+        }
+    }
+}
+
+fn node_classes(graphs: &[Graph], output: &str) -> Vec<(String, NodeClass)> {
+    let mut classes: Vec<(String, NodeClass)> = Vec::new();
+    let mut push = |node_name: String, class: NodeClass| {
+        if !classes.iter().any(|(n, _)| n == &node_name) {
+            classes.push((node_name, class));
+        }
+    };
+
+    for (graph_id, _, workflow) in graphs {
+        for step in &workflow.steps {
+            push(format!("{}_{}", graph_id, step.step_id), NodeClass::Step);
+
+            if !should_branch(step) {
+                continue;
+            }
+            push(format!("{}_{}Node", graph_id, step.step_id), NodeClass::Decision);
+
+            for action in step.on_success.iter().chain(step.on_failure.iter()).flatten() {
+                let node_name = format!("{}_{}", graph_id, action.name);
+                if action.criteria.is_some() {
+                    push(format!("{node_name}Node"), NodeClass::Decision);
+                }
+                if action.action_type == ActionType::Retry {
+                    let node_name =
+                        format!("{}_{}_{}", graph_id, step.step_id, action.name);
+                    push(format!("{node_name}RetryNode"), NodeClass::Retry);
                 }
             }
+        }
 
-            output.push_str("    end\n");
+        let end_node = EndNode {
+            node_name: graph_id,
+        };
+        if output.contains(&end_node.to_mermaid()) {
+            push(format!("{}EndNode", graph_id), NodeClass::End);
         }
+    }
 
-        output
+    for (_, _, workflow) in graphs {
+        for referenced in called_workflows(workflow) {
+            let node_name = workflow_node_name(referenced);
+            if !graphs.iter().any(|(id, _, _)| id == &node_name) {
+                push(node_name, NodeClass::CrossWorkflow);
+            }
+        }
+    }
+
+    classes
+}
+
+fn class_definitions(class_defs: &ClassDefs) -> String {
+    NodeClass::ALL
+        .iter()
+        .map(|class| {
+            format!(
+                "    classDef {class_name} {style}\n",
+                class_name = class.class_name(),
+                style = class.style(class_defs),
+            )
+        })
+        .collect()
+}
+
+fn class_assignments(classes: &[(String, NodeClass)]) -> String {
+    NodeClass::ALL
+        .iter()
+        .filter_map(|class| {
+            let node_names = classes
+                .iter()
+                .filter(|(_, c)| c == class)
+                .map(|(n, _)| n.as_str())
+                .collect::<Vec<&str>>();
+
+            (!node_names.is_empty()).then(|| {
+                format!(
+                    "    class {node_names} {class_name}\n",
+                    node_names = node_names.join(","),
+                    class_name = class.class_name(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// The workflows a filter selects, in document order, or every workflow without one.
+fn select_workflows<'a>(
+    arazzo: &'a ArazzoDocument,
+    filter: Option<&WorkflowFilter>,
+) -> Vec<&'a Workflow> {
+    let Some(filter) = filter else {
+        return arazzo.workflows.iter().collect();
+    };
+
+    let mut selected: Vec<&str> = Vec::new();
+    let mut queue: VecDeque<(&str, usize)> = filter
+        .workflow_ids
+        .iter()
+        .map(|workflow_id| (workflow_id.as_str(), 0))
+        .collect();
+
+    while let Some((workflow_id, depth)) = queue.pop_front() {
+        if selected.contains(&workflow_id) {
+            continue;
+        }
+        let Some(workflow) = arazzo.workflows.iter().find(|w| w.workflow_id == workflow_id)
+        else {
+            continue;
+        };
+        selected.push(&workflow.workflow_id);
+
+        if depth < filter.depth {
+            for referenced in referenced_workflows(workflow) {
+                queue.push_back((referenced, depth + 1));
+            }
+        }
+    }
+
+    arazzo
+        .workflows
+        .iter()
+        .filter(|w| selected.contains(&w.workflow_id.as_str()))
+        .collect()
+}
+
+fn referenced_workflows(workflow: &Workflow) -> Vec<&str> {
+    let depends_on = workflow.depends_on.iter().flatten().map(String::as_str);
+
+    called_workflows(workflow).into_iter().chain(depends_on).collect()
+}
+
+/// Node name of a workflow, turning `$sourceDescriptions.<name>.<workflowId>`
+/// references into `<name>_<workflowId>`.
+fn workflow_node_name(workflow_id: &str) -> String {
+    match parse_workflow_reference(workflow_id) {
+        Some((source, workflow_id)) => format!("{source}_{workflow_id}"),
+        None => workflow_id.to_string(),
     }
 }
 
+fn workflow_node_label(workflow_id: &str) -> String {
+    match parse_workflow_reference(workflow_id) {
+        Some((source, workflow_id)) => format!("{source}.{workflow_id}"),
+        None => workflow_id.to_string(),
+    }
+}
+
+fn called_workflows(workflow: &Workflow) -> Vec<&str> {
+    let invoked = workflow.steps.iter().filter_map(|step| step.workflow_id.as_deref());
+
+    let goto_targets = workflow
+        .steps
+        .iter()
+        .flat_map(|step| step.on_success.iter().chain(step.on_failure.iter()).flatten())
+        .filter(|action| action.action_type == ActionType::Goto)
+        .filter_map(|action| action.workflow_id.as_deref());
+
+    invoked.chain(goto_targets).collect()
+}
+
 fn render_actions(
     step_id: &str,
     success_criteria: &Option<Vec<Criteria>>,
     actions: &[Action],
     action_side: ActionSide,
     workflow_id: &str,
+    coverage: Option<&StepCoverage>,
+    edges: &mut Edges,
 ) -> String {
     let mut output = String::new();
 
     let mut from_rhombus_node_name = format!("{}_{}", workflow_id, step_id);
     let mut from_criteria = success_criteria.as_deref();
-    let mut verdict = match action_side {
-        ActionSide::OnSuccess => Verdict::Ok,
-        ActionSide::OnFailure => Verdict::Ng,
+    let (mut verdict, outcome) = match action_side {
+        ActionSide::OnSuccess => (Verdict::Ok, TraceOutcome::Success),
+        ActionSide::OnFailure => (Verdict::Ng, TraceOutcome::Failure),
     };
+    // Runs that reached the current decision, and runs that left it on `verdict`.
+    let mut reached = coverage.map(|c| c.hits);
+    let mut taken = coverage.map(|c| c.outcome(outcome));
 
     for (i, action) in actions.iter().enumerate() {
         let from_rhombus_node = RhombusNode {
@@ -149,13 +829,25 @@ fn render_actions(
             output.push_str(&to_rhombus_from_rhombus(
                 &from_rhombus_node,
                 &to_rhombus_node,
-                verdict,
+                Branch {
+                    verdict,
+                    hits: branch_hits(taken, reached),
+                },
+                edges,
             ));
 
             from_rhombus_node_name = to_rhombus_node_name;
             from_criteria = to_rhombus_node_criteria;
             verdict = Verdict::Ok;
+            reached = taken;
+            taken = coverage.map(|c| c.action(outcome, &action.name).unwrap_or(0));
+        } else if let Some(action_taken) = coverage.and_then(|c| c.action(outcome, &action.name)) {
+            taken = Some(action_taken);
         }
+        let branch = Branch {
+            verdict,
+            hits: branch_hits(taken, reached),
+        };
 
         let from_rhombus_node = RhombusNode {
             node_name: &from_rhombus_node_name,
@@ -168,10 +860,12 @@ fn render_actions(
                     output.push_str(&to_rectangle_from_rhombus(
                         &from_rhombus_node,
                         &RectangleNode {
-                            node_name: action_workflow_id,
+                            node_name: &workflow_node_name(action_workflow_id),
                             node_label: None,
+                            subroutine: false,
                         },
-                        verdict,
+                        branch,
+                        edges,
                     ));
                 } else if let Some(action_step_id) = action.step_id.as_deref() {
                     output.push_str(&to_rectangle_from_rhombus(
@@ -179,8 +873,10 @@ fn render_actions(
                         &RectangleNode {
                             node_name: format!("{}_{}", workflow_id, action_step_id).as_ref(),
                             node_label: None,
+                            subroutine: false,
                         },
-                        verdict,
+                        branch,
+                        edges,
                     ));
                 }
             }
@@ -190,12 +886,44 @@ fn render_actions(
                     &EndNode {
                         node_name: workflow_id,
                     },
-                    verdict,
+                    branch,
+                    edges,
+                ));
+            }
+            ActionType::Retry => {
+                let retry_node_name = format!("{}_{}_{}", workflow_id, step_id, action.name);
+                let retry_node = RetryNode {
+                    node_name: &retry_node_name,
+                    retry_after: action.retry_after,
+                    retry_limit: action.retry_limit,
+                };
+                let retry_target = if let Some(action_workflow_id) = action.workflow_id.as_deref() {
+                    workflow_node_name(action_workflow_id)
+                } else {
+                    let action_step_id = action.step_id.as_deref().unwrap_or(step_id);
+                    format!("{}_{}", workflow_id, action_step_id)
+                };
+
+                output.push_str(&to_retry_from_rhombus(
+                    &from_rhombus_node,
+                    &retry_node,
+                    branch,
+                    edges,
+                ));
+                output.push_str(&to_rectangle_from_retry(
+                    &retry_node,
+                    &RectangleNode {
+                        node_name: &retry_target,
+                        node_label: None,
+                        subroutine: false,
+                    },
+                    edges,
                 ));
             }
         }
 
         if has_criteria {
+            let remaining = taken.zip(reached).map(|(taken, reached)| reached - taken.min(reached));
             let is_last = i == actions.len() - 1;
             if is_last {
                 output.push_str(&to_end_from_rhombus(
@@ -203,10 +931,15 @@ fn render_actions(
                     &EndNode {
                         node_name: workflow_id,
                     },
-                    Verdict::Ng,
+                    Branch {
+                        verdict: Verdict::Ng,
+                        hits: branch_hits(remaining, reached),
+                    },
+                    edges,
                 ));
             } else {
                 verdict = Verdict::Ng;
+                taken = remaining;
             }
         }
     }
@@ -214,8 +947,51 @@ fn render_actions(
     output
 }
 
-fn should_branch(step: &Step) -> bool {
-    step.success_criteria.is_some() || step.on_success.is_some() || step.on_failure.is_some()
+fn step_label(step: &Step, flowchart: &MermaidFlowchart) -> Option<String> {
+    let operation = Operation::from_step(step).map(|o| flowchart.sources.enrich(o));
+
+    let label = match &flowchart.options.step_label {
+        StepLabel::Description => step.description.clone(),
+        StepLabel::Operation => operation.map(|o| operation_label(&o)),
+        StepLabel::Template(template) => {
+            let operation = operation.unwrap_or_default();
+            // Without a source its parentheses go too, as in `operation` labels.
+            let template = match operation.source {
+                Some(_) => template.clone(),
+                None => template.replace(" ({source})", "").replace("({source})", ""),
+            };
+            let label = template
+                .replace("{stepId}", &step.step_id)
+                .replace("{description}", step.description.as_deref().unwrap_or_default())
+                .replace("{operationId}", operation.operation_id.as_deref().unwrap_or_default())
+                .replace("{method}", operation.method.as_deref().unwrap_or_default())
+                .replace("{path}", operation.path.as_deref().unwrap_or_default())
+                .replace("{source}", operation.source.as_deref().unwrap_or_default())
+                .replace("{summary}", operation.summary.as_deref().unwrap_or_default())
+                .replace("{responses}", &operation.response_codes.join(", "))
+                .replace("{operation}", &operation_label(&operation));
+
+            (!label.trim().is_empty()).then_some(label)
+        }
+    };
+
+    label.or_else(|| step.workflow_id.as_deref().map(workflow_node_label))
+}
+
+fn operation_label(operation: &Operation) -> String {
+    let target = match (&operation.method, &operation.path) {
+        (Some(method), Some(path)) => format!("{method} {path}"),
+        _ => operation.operation_id.clone().unwrap_or_default(),
+    };
+
+    match &operation.source {
+        Some(source) => format!("{target} ({source})"),
+        None => target,
+    }
+}
+
+pub fn should_branch(step: &Step) -> bool {
+    step.success_criteria.is_some() || step.on_success.is_some() || step.on_failure.is_some()
 }
 
 fn title(graph_title: &str) -> String {
@@ -234,7 +1010,13 @@ fn subgraph_description(subgraph_description: Option<&str>) -> String {
     subgraph_description.map_or(String::new(), |v| format!("[\"{}\"]", v))
 }
 
-fn to_rectangle_from_rectangle(from: &RectangleNode, to: &RectangleNode) -> String {
+fn to_rectangle_from_rectangle(
+    from: &RectangleNode,
+    to: &RectangleNode,
+    edges: &mut Edges,
+) -> String {
+    edges.push(from.name(), to.name(), None, false);
+
     format!(
         "    {from_node} --> {to_node}\n",
         from_node = from.to_mermaid(),
@@ -242,19 +1024,25 @@ fn to_rectangle_from_rectangle(from: &RectangleNode, to: &RectangleNode) -> Stri
     )
 }
 
-fn to_rectangle_from_rhombus(from: &RhombusNode, to: &RectangleNode, verdict: Verdict) -> String {
+fn to_rectangle_from_rhombus(
+    from: &RhombusNode,
+    to: &RectangleNode,
+    branch: Branch,
+    edges: &mut Edges,
+) -> String {
+    edges.push(from.name(), to.name(), Some(branch), false);
+
     format!(
         "    {rhombus_node} -->|{verdict}| {rectangle_node}\n",
         rhombus_node = from.to_mermaid(),
         rectangle_node = to.to_mermaid(),
-        verdict = match verdict {
-            Verdict::Ok => "true",
-            Verdict::Ng => "false",
-        },
+        verdict = branch_label(branch),
     )
 }
 
-fn to_rhombus_from_rectangle(from: &RectangleNode, to: &RhombusNode) -> String {
+fn to_rhombus_from_rectangle(from: &RectangleNode, to: &RhombusNode, edges: &mut Edges) -> String {
+    edges.push(from.name(), to.name(), None, false);
+
     format!(
         "    {rectangle_node} --> {rhombus_node}\n",
         rectangle_node = from.to_mermaid(),
@@ -262,19 +1050,25 @@ fn to_rhombus_from_rectangle(from: &RectangleNode, to: &RhombusNode) -> String {
     )
 }
 
-fn to_rhombus_from_rhombus(from: &RhombusNode, to: &RhombusNode, verdict: Verdict) -> String {
+fn to_rhombus_from_rhombus(
+    from: &RhombusNode,
+    to: &RhombusNode,
+    branch: Branch,
+    edges: &mut Edges,
+) -> String {
+    edges.push(from.name(), to.name(), Some(branch), false);
+
     format!(
         "    {from_node} -->|{verdict}| {to_node}\n",
         from_node = from.to_mermaid(),
         to_node = to.to_mermaid(),
-        verdict = match verdict {
-            Verdict::Ok => "true",
-            Verdict::Ng => "false",
-        },
+        verdict = branch_label(branch),
     )
 }
 
-fn to_end_from_rectangle(from: &RectangleNode, to: &EndNode) -> String {
+fn to_end_from_rectangle(from: &RectangleNode, to: &EndNode, edges: &mut Edges) -> String {
+    edges.push(from.name(), to.name(), None, false);
+
     format!(
         "    {rectangle_node} --> {end_node}\n",
         rectangle_node = from.to_mermaid(),
@@ -282,24 +1076,201 @@ fn to_end_from_rectangle(from: &RectangleNode, to: &EndNode) -> String {
     )
 }
 
-fn to_end_from_rhombus(from: &RhombusNode, to: &EndNode, verdict: Verdict) -> String {
+fn to_end_from_rhombus(
+    from: &RhombusNode,
+    to: &EndNode,
+    branch: Branch,
+    edges: &mut Edges,
+) -> String {
+    edges.push(from.name(), to.name(), Some(branch), false);
+
     format!(
         "    {rhombus_node} -->|{verdict}| {end_node}\n",
         rhombus_node = from.to_mermaid(),
         end_node = to.to_mermaid(),
-        verdict = match verdict {
-            Verdict::Ok => "true",
-            Verdict::Ng => "false",
+        verdict = branch_label(branch),
+    )
+}
+
+fn describe_operation(step: &Step) -> Option<&str> {
+    step.operation_id
+        .as_deref()
+        .or(step.operation_path.as_deref())
+        .or(step.workflow_id.as_deref())
+}
+
+pub fn describe_criteria(criteria: &[Criteria]) -> String {
+    criteria
+        .iter()
+        .filter_map(describe_criterion)
+        .collect::<Vec<String>>()
+        .join(" && ")
+}
+
+/// Shows a criterion the way its type evaluates it: simple conditions as they are,
+/// regex as `context ~ /pattern/`, and JSONPath or XPath queries with their context.
+fn describe_criterion(criterion: &Criteria) -> Option<String> {
+    let condition = criterion.condition.as_deref()?;
+    let version = match &criterion.criteria_type {
+        Some(CriteriaType::Expression(expression_type)) => Some(expression_type.version.as_str()),
+        _ => None,
+    };
+
+    let query = |kind: &str| {
+        let kind = match version {
+            Some(version) => format!("{kind} {version}"),
+            None => kind.to_string(),
+        };
+        match criterion.context.as_deref() {
+            Some(context) => format!("{kind}: {condition} in {context}"),
+            None => format!("{kind}: {condition}"),
+        }
+    };
+
+    Some(match criteria_type_name(criterion) {
+        CriteriaTypeName::Simple => match condition.parse::<Condition>() {
+            Ok(condition) => condition.to_string(),
+            Err(_) => condition.to_string(),
+        },
+        CriteriaTypeName::Regex => match criterion.context.as_deref() {
+            Some(context) => format!("{context} ~ /{condition}/"),
+            None => format!("/{condition}/"),
         },
+        CriteriaTypeName::JsonPath => query("jsonpath"),
+        CriteriaTypeName::XPath => query("xpath"),
+    })
+}
+
+fn describe_action(action: &Action) -> String {
+    let target = match action.action_type {
+        ActionType::Goto => match (action.workflow_id.as_deref(), action.step_id.as_deref()) {
+            (Some(workflow_id), _) => format!("goto workflow {workflow_id}"),
+            (None, Some(step_id)) => format!("goto {step_id}"),
+            (None, None) => String::from("goto"),
+        },
+        ActionType::End => String::from("end"),
+        ActionType::Retry => retry_label(action.retry_after, action.retry_limit).to_lowercase(),
+    };
+
+    match action.criteria.as_deref().map(describe_criteria) {
+        Some(condition) if !condition.is_empty() => format!("{target} if {condition}"),
+        _ => target,
+    }
+}
+
+fn describe_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(v) => v.clone(),
+        v => v.to_string(),
+    }
+}
+
+fn to_workflow_from_subroutine(
+    from_node_name: &str,
+    to_node_name: &str,
+    parameters: &[String],
+    edges: &mut Edges,
+) -> String {
+    edges.push(from_node_name.to_string(), to_node_name.to_string(), None, true);
+
+    let parameters = parameters.join("<br>").replace('"', "#quot;");
+
+    if parameters.is_empty() {
+        format!("    {from_node_name} -.-> {to_node_name}\n")
+    } else {
+        format!("    {from_node_name} -.->|\"{parameters}\"| {to_node_name}\n")
+    }
+}
+
+fn to_retry_from_rhombus(
+    from: &RhombusNode,
+    to: &RetryNode,
+    branch: Branch,
+    edges: &mut Edges,
+) -> String {
+    edges.push(from.name(), to.name(), Some(branch), false);
+
+    format!(
+        "    {rhombus_node} -->|{verdict}| {retry_node}\n",
+        rhombus_node = from.to_mermaid(),
+        retry_node = to.to_mermaid(),
+        verdict = branch_label(branch),
     )
 }
 
-#[derive(Clone, Copy)]
+fn to_rectangle_from_retry(from: &RetryNode, to: &RectangleNode, edges: &mut Edges) -> String {
+    edges.push(from.name(), to.name(), None, false);
+
+    format!(
+        "    {retry_node} --> {rectangle_node}\n",
+        retry_node = from.to_mermaid(),
+        rectangle_node = to.to_mermaid(),
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
 enum Verdict {
     Ok,
     Ng,
 }
 
+impl Verdict {
+    fn label(self) -> &'static str {
+        match self {
+            Verdict::Ok => "true",
+            Verdict::Ng => "false",
+        }
+    }
+}
+
+/// A link leaving a decision, with how often it was taken when coverage is given.
+#[derive(Clone, Copy)]
+struct Branch {
+    verdict: Verdict,
+    hits: Option<Hits>,
+}
+
+fn branch_label(branch: Branch) -> String {
+    let verdict = branch.verdict.label();
+
+    match branch.hits {
+        Some(hits) => format!("\"{verdict} {hits}\""),
+        None => verdict.to_string(),
+    }
+}
+
+fn branch_hits(taken: Option<u64>, reached: Option<u64>) -> Option<Hits> {
+    taken.zip(reached).map(|(taken, reached)| Hits { taken, reached })
+}
+
+/// A link of the flowchart, in the order Mermaid numbers them for `linkStyle`.
+struct Edge {
+    graph_id: Option<String>,
+    from: String,
+    to: String,
+    branch: Option<Branch>,
+    dotted: bool,
+}
+
+/// Records links as they are drawn, tagged with the subgraph drawing them.
+#[derive(Default)]
+struct Edges {
+    graph_id: Option<String>,
+    edges: Vec<Edge>,
+}
+
+impl Edges {
+    fn push(&mut self, from: String, to: String, branch: Option<Branch>, dotted: bool) {
+        self.edges.push(Edge {
+            graph_id: self.graph_id.clone(),
+            from,
+            to,
+            branch,
+            dotted,
+        });
+    }
+}
+
 #[derive(Clone, Copy)]
 enum ActionSide {
     OnSuccess,
@@ -307,26 +1278,36 @@ enum ActionSide {
 }
 
 trait Node {
+    fn name(&self) -> String;
     fn to_mermaid(&self) -> String;
 }
 
 struct RectangleNode<'a> {
     node_name: &'a str,
     node_label: Option<&'a str>,
+    subroutine: bool,
 }
 
 impl<'a> Node for RectangleNode<'a> {
+    fn name(&self) -> String {
+        self.node_name.to_string()
+    }
+
     fn to_mermaid(&self) -> String {
         format!(
             "{node_name}{node_label}",
             node_name = self.node_name,
-            node_label = rectangle_node_label(self.node_label),
+            node_label = rectangle_node_label(self.node_label, self.subroutine),
         )
     }
 }
 
-fn rectangle_node_label(node_label: Option<&str>) -> String {
-    node_label.map_or(String::new(), |v| format!("[\"{}\"]", v))
+fn rectangle_node_label(node_label: Option<&str>, subroutine: bool) -> String {
+    match (node_label, subroutine) {
+        (Some(v), true) => format!("[[\"{}\"]]", v),
+        (Some(v), false) => format!("[\"{}\"]", v),
+        (None, _) => String::new(),
+    }
 }
 
 struct RhombusNode<'a> {
@@ -335,6 +1316,10 @@ struct RhombusNode<'a> {
 }
 
 impl<'a> Node for RhombusNode<'a> {
+    fn name(&self) -> String {
+        format!("{}Node", self.node_name)
+    }
+
     fn to_mermaid(&self) -> String {
         format!(
             "{node_name}Node{condition}",
@@ -348,14 +1333,12 @@ fn rhombus_node_condition(criteria: &Option<&[Criteria]>) -> String {
     if let Some(criteria) = &criteria
         && !criteria.is_empty()
     {
-        let condition = criteria
-            .iter()
-            .filter_map(|c| c.condition.as_deref())
-            .collect::<Vec<&str>>()
-            .join(" && ");
+        let condition = describe_criteria(criteria);
 
         if condition.is_empty() {
             String::new()
+        } else if condition.contains(['(', ')', '[', ']', '{', '}', '"']) {
+            format!("{{\"{}\"}}", condition.replace('"', "#quot;"))
         } else {
             format!("{{{condition}}}")
         }
@@ -369,38 +1352,110 @@ struct EndNode<'a> {
 }
 
 impl Node for EndNode<'_> {
+    fn name(&self) -> String {
+        format!("{}EndNode", self.node_name)
+    }
+
     fn to_mermaid(&self) -> String {
         format!("{node_name}EndNode((End))", node_name = self.node_name)
     }
 }
 
+struct RetryNode<'a> {
+    node_name: &'a str,
+    retry_after: Option<f64>,
+    retry_limit: Option<u32>,
+}
+
+impl Node for RetryNode<'_> {
+    fn name(&self) -> String {
+        format!("{}RetryNode", self.node_name)
+    }
+
+    fn to_mermaid(&self) -> String {
+        format!(
+            "{node_name}RetryNode([\"{label}\"])",
+            node_name = self.node_name,
+            label = retry_label(self.retry_after, self.retry_limit),
+        )
+    }
+}
+
+fn retry_label(retry_after: Option<f64>, retry_limit: Option<u32>) -> String {
+    let mut label = String::from("Retry");
+    if let Some(retry_after) = retry_after {
+        label.push_str(&format!(" after {retry_after}s"));
+    }
+    if let Some(retry_limit) = retry_limit {
+        label.push_str(&format!(" up to {retry_limit} times"));
+    }
+
+    label
+}
+
+struct CollapsedNode<'a> {
+    node_name: &'a str,
+    node_label: &'a str,
+}
+
+impl Node for CollapsedNode<'_> {
+    fn name(&self) -> String {
+        self.node_name.to_string()
+    }
+
+    fn to_mermaid(&self) -> String {
+        format!(
+            "{node_name}([\"{node_label}\"])",
+            node_name = self.node_name,
+            node_label = self.node_label,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::arazzo::{Criteria, Info, Workflow};
+    use crate::arazzo::{CriterionExpressionType, Info, Parameter, StepParameter};
 
     #[test]
     fn render_full() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![
                 Workflow {
                     workflow_id: String::from("workflowFoo"),
+                    summary: None,
                     description: Some(String::from("Workflow foo's description.")),
+                    inputs: None,
+                    depends_on: None,
                     steps: vec![
                         Step {
                             step_id: String::from("stepFoo"),
                             description: Some(String::from("Step foo's description.")),
+                            operation_id: None,
+                            operation_path: None,
+                            workflow_id: None,
+                            parameters: None,
+                            request_body: None,
                             success_criteria: Some(vec![Criteria {
+                                context: None,
                                 condition: Some(String::from("$statusCode == 200")),
+                                criteria_type: None,
                             }]),
                             on_success: Some(vec![Action {
                                 name: String::from("proceedToStepBar"),
                                 action_type: ActionType::Goto,
                                 workflow_id: None,
                                 step_id: Some(String::from("stepBar")),
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
                             on_failure: Some(vec![Action {
@@ -408,20 +1463,32 @@ mod tests {
                                 action_type: ActionType::Goto,
                                 workflow_id: None,
                                 step_id: Some(String::from("stepBaz")),
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
+                            outputs: None,
                         },
                         Step {
                             step_id: String::from("stepBar"),
                             description: Some(String::from("Step bar's description.")),
+                            operation_id: None,
+                            operation_path: None,
+                            workflow_id: None,
+                            parameters: None,
+                            request_body: None,
                             success_criteria: Some(vec![Criteria {
+                                context: None,
                                 condition: Some(String::from("$statusCode == 200")),
+                                criteria_type: None,
                             }]),
                             on_success: Some(vec![Action {
                                 name: String::from("done"),
                                 action_type: ActionType::End,
                                 workflow_id: None,
                                 step_id: None,
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
                             on_failure: Some(vec![Action {
@@ -429,45 +1496,73 @@ mod tests {
                                 action_type: ActionType::Goto,
                                 workflow_id: None,
                                 step_id: Some(String::from("stepBaz")),
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
+                            outputs: None,
                         },
                         Step {
                             step_id: String::from("stepBaz"),
                             description: Some(String::from("Step baz's description.")),
+                            operation_id: None,
+                            operation_path: None,
+                            workflow_id: None,
+                            parameters: None,
+                            request_body: None,
                             success_criteria: Some(vec![Criteria {
+                                context: None,
                                 condition: Some(String::from("$statusCode == 200")),
+                                criteria_type: None,
                             }]),
                             on_success: Some(vec![Action {
                                 name: String::from("proceedToWorkflowBar"),
                                 action_type: ActionType::Goto,
                                 workflow_id: Some(String::from("workflowBar")),
                                 step_id: None,
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
                             on_failure: None,
+                            outputs: None,
                         },
                     ],
+                    outputs: None,
                 },
                 Workflow {
                     workflow_id: String::from("workflowBar"),
+                    summary: None,
                     description: Some(String::from("Workflow bar's description.")),
+                    inputs: None,
+                    depends_on: None,
                     steps: vec![
                         Step {
                             step_id: String::from("stepFoo"),
                             description: Some(String::from("Step foo's description.")),
+                            operation_id: None,
+                            operation_path: None,
+                            workflow_id: None,
+                            parameters: None,
+                            request_body: None,
                             success_criteria: Some(vec![Criteria {
+                                context: None,
                                 condition: Some(String::from("$statusCode == 200")),
+                                criteria_type: None,
                             }]),
                             on_success: Some(vec![Action {
                                 name: String::from("proceedToStepBar"),
                                 action_type: ActionType::Goto,
                                 workflow_id: None,
                                 step_id: Some(String::from("stepBar")),
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: Some(vec![Criteria {
+                                    context: None,
                                     condition: Some(String::from(
                                         "$response.body.status == 'approved'",
                                     )),
+                                    criteria_type: None,
                                 }]),
                             }]),
                             on_failure: Some(vec![Action {
@@ -475,22 +1570,36 @@ mod tests {
                                 action_type: ActionType::Goto,
                                 workflow_id: None,
                                 step_id: Some(String::from("stepBaz")),
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: Some(vec![Criteria {
+                                    context: None,
                                     condition: Some(String::from("$response.body.error != null")),
+                                    criteria_type: None,
                                 }]),
                             }]),
+                            outputs: None,
                         },
                         Step {
                             step_id: String::from("stepBar"),
                             description: Some(String::from("Step bar's description.")),
+                            operation_id: None,
+                            operation_path: None,
+                            workflow_id: None,
+                            parameters: None,
+                            request_body: None,
                             success_criteria: Some(vec![Criteria {
+                                context: None,
                                 condition: Some(String::from("$statusCode == 200")),
+                                criteria_type: None,
                             }]),
                             on_success: Some(vec![Action {
                                 name: String::from("done"),
                                 action_type: ActionType::End,
                                 workflow_id: None,
                                 step_id: None,
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
                             on_failure: Some(vec![Action {
@@ -498,22 +1607,32 @@ mod tests {
                                 action_type: ActionType::Goto,
                                 workflow_id: None,
                                 step_id: Some(String::from("stepBaz")),
+                                retry_after: None,
+                                retry_limit: None,
                                 criteria: None,
                             }]),
+                            outputs: None,
                         },
                         Step {
                             step_id: String::from("stepBaz"),
                             description: Some(String::from("Step baz's description.")),
+                            operation_id: None,
+                            operation_path: None,
+                            workflow_id: None,
+                            parameters: None,
+                            request_body: None,
                             success_criteria: None,
                             on_success: None,
                             on_failure: None,
+                            outputs: None,
                         },
                     ],
+                    outputs: None,
                 },
             ],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -557,34 +1676,59 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![
                 Workflow {
                     workflow_id: String::from("workflowFoo"),
+                    summary: None,
                     description: None,
+                    inputs: None,
+                    depends_on: None,
                     steps: vec![Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     }],
+                    outputs: None,
                 },
                 Workflow {
                     workflow_id: String::from("workflowBar"),
+                    summary: None,
                     description: None,
+                    inputs: None,
+                    depends_on: None,
                     steps: vec![Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     }],
+                    outputs: None,
                 },
             ],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -610,30 +1754,51 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -657,21 +1822,36 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![Step {
                     step_id: String::from("stepFoo"),
                     description: None,
+                    operation_id: None,
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
                     success_criteria: None,
                     on_success: None,
                     on_failure: None,
+                    outputs: None,
                 }],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -694,22 +1874,39 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![Criteria {
+                            context: None,
                             condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
                         }]),
                         on_success: Some(vec![Action {
                             name: String::from("proceedToStepBar"),
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
                         on_failure: Some(vec![Action {
@@ -717,28 +1914,44 @@ mod tests {
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBaz")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBaz"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -765,38 +1978,63 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![Criteria {
+                            context: None,
                             condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
                         }]),
                         on_success: Some(vec![Action {
                             name: String::from("proceedToStepBar"),
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -822,16 +2060,31 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![Criteria {
+                            context: None,
                             condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
                         }]),
                         on_success: None,
                         on_failure: Some(vec![Action {
@@ -839,28 +2092,44 @@ mod tests {
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBaz")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBaz"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -887,32 +2156,55 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![Criteria {
+                            context: None,
                             condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
                         }]),
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -938,20 +2230,35 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: Some(vec![Action {
                             name: String::from("proceedToStepBar"),
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
                         on_failure: Some(vec![Action {
@@ -959,28 +2266,44 @@ mod tests {
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBaz")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBaz"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1007,36 +2330,59 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: Some(vec![Action {
                             name: String::from("proceedToStepBar"),
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1062,14 +2408,27 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: Some(vec![Action {
@@ -1077,28 +2436,44 @@ mod tests {
                             action_type: ActionType::Goto,
                             workflow_id: None,
                             step_id: Some(String::from("stepBaz")),
+                            retry_after: None,
+                            retry_limit: None,
                             criteria: None,
                         }]),
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBaz"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1125,30 +2500,51 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1172,37 +2568,62 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![
                             Criteria {
+                                context: None,
                                 condition: Some(String::from("$statusCode == 200")),
+                                criteria_type: None,
                             },
                             Criteria {
+                                context: None,
                                 condition: Some(String::from("$response.body.status == done")),
+                                criteria_type: None,
                             },
                         ]),
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1228,30 +2649,51 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![]),
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1277,29 +2719,48 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![Step {
                     step_id: String::from("stepFoo"),
                     description: None,
+                    operation_id: None,
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
                     success_criteria: Some(vec![Criteria {
+                        context: None,
                         condition: Some(String::from("$statusCode == 200")),
+                        criteria_type: None,
                     }]),
                     on_success: Some(vec![Action {
                         name: String::from("done"),
                         action_type: ActionType::End,
                         workflow_id: None,
                         step_id: None,
+                        retry_after: None,
+                        retry_limit: None,
                         criteria: None,
                     }]),
                     on_failure: None,
+                    outputs: None,
                 }],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1324,23 +2785,40 @@ mod tests {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![Step {
                     step_id: String::from("stepFoo"),
                     description: None,
+                    operation_id: None,
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
                     success_criteria: Some(vec![Criteria {
+                        context: None,
                         condition: Some(String::from("$statusCode == 200")),
+                        criteria_type: None,
                     }]),
                     on_success: None,
                     on_failure: None,
+                    outputs: None,
                 }],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1356,132 +2834,1465 @@ mod tests {
             "    end\n",
         );
 
-        println!("{}", actual);
-        assert_eq!(expected, actual);
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: None,
+                            step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![Criteria {
+                                context: None,
+                                condition: Some(String::from(
+                                    "$response.body.status == 'approved'",
+                                )),
+                                criteria_type: None,
+                            }]),
+                        }]),
+                        on_failure: Some(vec![Action {
+                            name: String::from("done"),
+                            action_type: ActionType::End,
+                            workflow_id: None,
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![Criteria {
+                                context: None,
+                                condition: Some(String::from("$response.body.error != null")),
+                                criteria_type: None,
+                            }]),
+                        }]),
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBaz"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_doneNode{$response.body.error != null}\n",
+            "    workflowFoo_doneNode{$response.body.error != null} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_doneNode{$response.body.error != null} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFoo_stepBaz\n",
+            "    workflowFoo_stepBaz --> workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_goto() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: None,
+                            step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![Criteria {
+                                context: None,
+                                condition: Some(String::from(
+                                    "$response.body.status == 'approved'",
+                                )),
+                                criteria_type: None,
+                            }]),
+                        }]),
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_end() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![Step {
+                    step_id: String::from("stepFoo"),
+                    description: None,
+                    operation_id: None,
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
+                    success_criteria: Some(vec![Criteria {
+                        context: None,
+                        condition: Some(String::from("$statusCode == 200")),
+                        criteria_type: None,
+                    }]),
+                    on_success: Some(vec![Action {
+                        name: String::from("done"),
+                        action_type: ActionType::End,
+                        workflow_id: None,
+                        step_id: None,
+                        retry_after: None,
+                        retry_limit: None,
+                        criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$response.body.status == 'approved'")),
+                            criteria_type: None,
+                        }]),
+                    }]),
+                    on_failure: None,
+                    outputs: None,
+                }],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_doneNode{$response.body.status == 'approved'}\n",
+            "    workflowFoo_doneNode{$response.body.status == 'approved'} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_doneNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_on_success_only() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: None,
+                            step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![Criteria {
+                                context: None,
+                                condition: Some(String::from(
+                                    "$response.body.status == 'approved'",
+                                )),
+                                criteria_type: None,
+                            }]),
+                        }]),
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_on_failure_only() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: None,
+                        on_failure: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: None,
+                            step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![Criteria {
+                                context: None,
+                                condition: Some(String::from("$response.body.error != null")),
+                                criteria_type: None,
+                            }]),
+                        }]),
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_proceedToStepBarNode{$response.body.error != null}\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.error != null} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.error != null} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_with_empty_criteria() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: None,
+                            step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![]),
+                        }]),
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode\n",
+            "    workflowFoo_proceedToStepBarNode -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_proceedToStepBarNode -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_with_multiple_action_criteria() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: None,
+                            step_id: Some(String::from("stepBar")),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![
+                                Criteria {
+                                    context: None,
+                                    condition: Some(String::from(
+                                        "$response.body.status == 'approved'",
+                                    )),
+                                    criteria_type: None,
+                                },
+                                Criteria {
+                                    context: None,
+                                    condition: Some(String::from("$response.body.error == null")),
+                                    criteria_type: None,
+                                },
+                            ]),
+                        }]),
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode\n",
+            "    workflowFoo_stepFooNode -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved' && $response.body.error == null}\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved' && $response.body.error == null} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved' && $response.body.error == null} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_action_goto_another_workflow() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![
+                Workflow {
+                    workflow_id: String::from("workflowFoo"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToStepBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: Some(String::from("workflowBar")),
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: None,
+                        }]),
+                        on_failure: Some(vec![Action {
+                            name: String::from("proceedToStepBaz"),
+                            action_type: ActionType::Goto,
+                            workflow_id: Some(String::from("workflowBaz")),
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: None,
+                        }]),
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBar"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBaz"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+            ],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode\n",
+            "    workflowFoo_stepFooNode -->|true| workflowBar\n",
+            "    workflowFoo_stepFooNode -->|false| workflowBaz\n",
+            "    end\n",
+            "    subgraph workflowBar\n",
+            "    workflowBar_stepFoo --> workflowBarEndNode((End))\n",
+            "    end\n",
+            "    subgraph workflowBaz\n",
+            "    workflowBaz_stepFoo --> workflowBazEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_actions() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: Some(vec![
+                            Action {
+                                name: String::from("proceedToStepBar"),
+                                action_type: ActionType::Goto,
+                                workflow_id: None,
+                                step_id: Some(String::from("stepBar")),
+                                retry_after: None,
+                                retry_limit: None,
+                                criteria: Some(vec![Criteria {
+                                    context: None,
+                                    condition: Some(String::from(
+                                        "$response.body.status == 'approved'",
+                                    )),
+                                    criteria_type: None,
+                                }]),
+                            },
+                            Action {
+                                name: String::from("proceedToStepBaz"),
+                                action_type: ActionType::Goto,
+                                workflow_id: None,
+                                step_id: Some(String::from("stepBaz")),
+                                retry_after: None,
+                                retry_limit: None,
+                                criteria: Some(vec![Criteria {
+                                    context: None,
+                                    condition: Some(String::from(
+                                        "$response.body.status == 'declined'",
+                                    )),
+                                    criteria_type: None,
+                                }]),
+                            },
+                        ]),
+                        on_failure: Some(vec![Action {
+                            name: String::from("done"),
+                            action_type: ActionType::End,
+                            workflow_id: None,
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![Criteria {
+                                context: None,
+                                condition: Some(String::from("$response.body.error != null")),
+                                criteria_type: None,
+                            }]),
+                        }]),
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBaz"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+        "---\n",
+        "title: Workflows\n",
+        "---\n",
+        "flowchart TD\n",
+        "    subgraph workflowFoo\n",
+        "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+        "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
+        "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
+        "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFoo_proceedToStepBazNode{$response.body.status == 'declined'}\n",
+        "    workflowFoo_proceedToStepBazNode{$response.body.status == 'declined'} -->|true| workflowFoo_stepBaz\n",
+        "    workflowFoo_proceedToStepBazNode{$response.body.status == 'declined'} -->|false| workflowFooEndNode((End))\n",
+        "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_doneNode{$response.body.error != null}\n",
+        "    workflowFoo_doneNode{$response.body.error != null} -->|true| workflowFooEndNode((End))\n",
+        "    workflowFoo_doneNode{$response.body.error != null} -->|false| workflowFooEndNode((End))\n",
+        "    workflowFoo_stepBar --> workflowFoo_stepBaz\n",
+        "    workflowFoo_stepBaz --> workflowFooEndNode((End))\n",
+        "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_on_actions_with_empty_criteria() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: None,
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 200")),
+                            criteria_type: None,
+                        }]),
+                        on_success: Some(vec![
+                            Action {
+                                name: String::from("proceedToStepBar"),
+                                action_type: ActionType::Goto,
+                                workflow_id: None,
+                                step_id: Some(String::from("stepBar")),
+                                retry_after: None,
+                                retry_limit: None,
+                                criteria: Some(vec![]),
+                            },
+                            Action {
+                                name: String::from("proceedToStepBaz"),
+                                action_type: ActionType::Goto,
+                                workflow_id: None,
+                                step_id: Some(String::from("stepBaz")),
+                                retry_after: None,
+                                retry_limit: None,
+                                criteria: Some(vec![]),
+                            },
+                        ]),
+                        on_failure: Some(vec![Action {
+                            name: String::from("done"),
+                            action_type: ActionType::End,
+                            workflow_id: None,
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: Some(vec![]),
+                        }]),
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                    Step {
+                        step_id: String::from("stepBaz"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    },
+                ],
+                outputs: None,
+            }],
+        };
+
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+        "---\n",
+        "title: Workflows\n",
+        "---\n",
+        "flowchart TD\n",
+        "    subgraph workflowFoo\n",
+        "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+        "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode\n",
+        "    workflowFoo_proceedToStepBarNode -->|true| workflowFoo_stepBar\n",
+        "    workflowFoo_proceedToStepBarNode -->|false| workflowFoo_proceedToStepBazNode\n",
+        "    workflowFoo_proceedToStepBazNode -->|true| workflowFoo_stepBaz\n",
+        "    workflowFoo_proceedToStepBazNode -->|false| workflowFooEndNode((End))\n",
+        "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_doneNode\n",
+        "    workflowFoo_doneNode -->|true| workflowFooEndNode((End))\n",
+        "    workflowFoo_doneNode -->|false| workflowFooEndNode((End))\n",
+        "    workflowFoo_stepBar --> workflowFoo_stepBaz\n",
+        "    workflowFoo_stepBaz --> workflowFooEndNode((End))\n",
+        "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_workflow_filter() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![
+                Workflow {
+                    workflow_id: String::from("workflowFoo"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToWorkflowBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: Some(String::from("workflowBar")),
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: None,
+                        }]),
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBar"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: Some(vec![String::from("workflowBaz")]),
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBaz"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+            ],
+        };
+
+        let sut = MermaidFlowchart {
+            workflow_filter: Some(WorkflowFilter {
+                workflow_ids: vec![String::from("workflowFoo")],
+                depth: 0,
+            }),
+            options: FlowchartOptions::default(),
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::default(),
+            trace: None,
+            coverage: None,
+            diff: None,
+        };
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode\n",
+            "    workflowFoo_stepFooNode -->|true| workflowBar\n",
+            "    workflowFoo_stepFooNode -->|false| workflowFooEndNode((End))\n",
+            "    end\n",
+            "    workflowBar([\"workflowBar\"])\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_workflow_filter_with_depth() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![
+                Workflow {
+                    workflow_id: String::from("workflowFoo"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: Some(vec![Action {
+                            name: String::from("proceedToWorkflowBar"),
+                            action_type: ActionType::Goto,
+                            workflow_id: Some(String::from("workflowBar")),
+                            step_id: None,
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: None,
+                        }]),
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBar"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: Some(vec![String::from("workflowBaz")]),
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBaz"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+            ],
+        };
+
+        let sut = MermaidFlowchart {
+            workflow_filter: Some(WorkflowFilter {
+                workflow_ids: vec![String::from("workflowFoo")],
+                depth: 2,
+            }),
+            options: FlowchartOptions::default(),
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::default(),
+            trace: None,
+            coverage: None,
+            diff: None,
+        };
+
+        let actual = sut
+            .selected_workflows(&arazzo)
+            .iter()
+            .map(|w| w.workflow_id.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(vec!["workflowFoo", "workflowBar", "workflowBaz"], actual);
+
+        let sut = MermaidFlowchart {
+            workflow_filter: Some(WorkflowFilter {
+                workflow_ids: vec![String::from("workflowBar")],
+                depth: 1,
+            }),
+            options: FlowchartOptions::default(),
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::default(),
+            trace: None,
+            coverage: None,
+            diff: None,
+        };
+
+        let actual = sut
+            .selected_workflows(&arazzo)
+            .iter()
+            .map(|w| w.workflow_id.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(vec!["workflowBar", "workflowBaz"], actual);
     }
 
     #[test]
-    fn render_on_action() {
+    fn render_split() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
-            },
-            workflows: vec![Workflow {
-                workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
-                steps: vec![
-                    Step {
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![
+                Workflow {
+                    workflow_id: String::from("workflowFoo"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
                         step_id: String::from("stepFoo"),
                         description: None,
-                        success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
                         on_success: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
+                            name: String::from("proceedToWorkflowBar"),
                             action_type: ActionType::Goto,
-                            workflow_id: None,
-                            step_id: Some(String::from("stepBar")),
-                            criteria: Some(vec![Criteria {
-                                condition: Some(String::from(
-                                    "$response.body.status == 'approved'",
-                                )),
-                            }]),
-                        }]),
-                        on_failure: Some(vec![Action {
-                            name: String::from("done"),
-                            action_type: ActionType::End,
-                            workflow_id: None,
+                            workflow_id: Some(String::from("workflowBar")),
                             step_id: None,
-                            criteria: Some(vec![Criteria {
-                                condition: Some(String::from("$response.body.error != null")),
-                            }]),
+                            retry_after: None,
+                            retry_limit: None,
+                            criteria: None,
                         }]),
-                    },
-                    Step {
-                        step_id: String::from("stepBar"),
+                        on_failure: None,
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBar"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: Some(vec![String::from("workflowBaz")]),
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
-                    },
-                    Step {
-                        step_id: String::from("stepBaz"),
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+                Workflow {
+                    workflow_id: String::from("workflowBaz"),
+                    summary: None,
+                    description: None,
+                    inputs: None,
+                    depends_on: None,
+                    steps: vec![Step {
+                        step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
-                    },
-                ],
-            }],
+                        outputs: None,
+                    }],
+                    outputs: None,
+                },
+            ],
         };
+        let sut = MermaidFlowchart::builder().build();
+
+        let actual = sut.render_split(&arazzo, &|workflow_id| format!("{workflow_id}.mmd"));
+
+        let expected = vec![
+            (
+                "workflowFoo",
+                String::from(concat!(
+                    "---\n",
+                    "title: Workflows - workflowFoo\n",
+                    "---\n",
+                    "flowchart TD\n",
+                    "    subgraph workflowFoo\n",
+                    "    workflowFoo_stepFoo --> workflowFoo_stepFooNode\n",
+                    "    workflowFoo_stepFooNode -->|true| workflowBar\n",
+                    "    workflowFoo_stepFooNode -->|false| workflowFooEndNode((End))\n",
+                    "    end\n",
+                    "    workflowBar([\"workflowBar\"])\n",
+                    "    click workflowBar href \"workflowBar.mmd\"\n",
+                )),
+            ),
+            (
+                "workflowBar",
+                String::from(concat!(
+                    "---\n",
+                    "title: Workflows - workflowBar\n",
+                    "---\n",
+                    "flowchart TD\n",
+                    "    subgraph workflowBar\n",
+                    "    workflowBar_stepFoo --> workflowBarEndNode((End))\n",
+                    "    end\n",
+                )),
+            ),
+            (
+                "workflowBaz",
+                String::from(concat!(
+                    "---\n",
+                    "title: Workflows - workflowBaz\n",
+                    "---\n",
+                    "flowchart TD\n",
+                    "    subgraph workflowBaz\n",
+                    "    workflowBaz_stepFoo --> workflowBazEndNode((End))\n",
+                    "    end\n",
+                )),
+            ),
+        ];
 
-        let sut = MermaidFlowchart;
-
-        let actual = sut.render(&arazzo);
-
-        let expected = concat!(
-            "---\n",
-            "title: Workflows\n",
-            "---\n",
-            "flowchart TD\n",
-            "    subgraph workflowFoo\n",
-            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_doneNode{$response.body.error != null}\n",
-            "    workflowFoo_doneNode{$response.body.error != null} -->|true| workflowFooEndNode((End))\n",
-            "    workflowFoo_doneNode{$response.body.error != null} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepBar --> workflowFoo_stepBaz\n",
-            "    workflowFoo_stepBaz --> workflowFooEndNode((End))\n",
-            "    end\n",
-        );
-
-        println!("{}", actual);
         assert_eq!(expected, actual);
     }
 
     #[test]
-    fn render_on_action_goto() {
+    fn render_criteria_types() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
                         description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
-                        on_success: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
-                            action_type: ActionType::Goto,
-                            workflow_id: None,
-                            step_id: Some(String::from("stepBar")),
-                            criteria: Some(vec![Criteria {
-                                condition: Some(String::from(
-                                    "$response.body.status == 'approved'",
-                                )),
-                            }]),
+                            context: Some(String::from("$response.body")),
+                            condition: Some(String::from("^ok")),
+                            criteria_type: Some(CriteriaType::Name(CriteriaTypeName::Regex)),
                         }]),
+                        on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
-                        success_criteria: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: Some(vec![Criteria {
+                            context: Some(String::from("$response.body")),
+                            condition: Some(String::from("$.items[?(@.id)]")),
+                            criteria_type: Some(CriteriaType::Expression(CriterionExpressionType {
+                                name: CriteriaTypeName::JsonPath,
+                                version: String::from("draft-goessner-dispatch-jsonpath-00"),
+                            })),
+                        }]),
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
-
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1491,12 +4302,12 @@ mod tests {
             "---\n",
             "flowchart TD\n",
             "    subgraph workflowFoo\n",
-            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$response.body ~ /^ok/}\n",
+            "    workflowFoo_stepFooNode{$response.body ~ /^ok/} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_stepFooNode{$response.body ~ /^ok/} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBar --> workflowFoo_stepBarNode{\"jsonpath draft-goessner-dispatch-jsonpath-00: $.items[?(@.id)] in $response.body\"}\n",
+            "    workflowFoo_stepBarNode{\"jsonpath draft-goessner-dispatch-jsonpath-00: $.items[?(@.id)] in $response.body\"} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepBarNode{\"jsonpath draft-goessner-dispatch-jsonpath-00: $.items[?(@.id)] in $response.body\"} -->|false| workflowFooEndNode((End))\n",
             "    end\n",
         );
 
@@ -1505,35 +4316,51 @@ mod tests {
     }
 
     #[test]
-    fn render_on_action_end() {
+    fn render_on_action_retry() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![Step {
                     step_id: String::from("stepFoo"),
                     description: None,
+                    operation_id: None,
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
                     success_criteria: Some(vec![Criteria {
+                        context: None,
                         condition: Some(String::from("$statusCode == 200")),
+                        criteria_type: None,
                     }]),
-                    on_success: Some(vec![Action {
-                        name: String::from("done"),
-                        action_type: ActionType::End,
+                    on_success: None,
+                    on_failure: Some(vec![Action {
+                        name: String::from("retryFoo"),
+                        action_type: ActionType::Retry,
                         workflow_id: None,
                         step_id: None,
-                        criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$response.body.status == 'approved'")),
-                        }]),
+                        retry_after: Some(1.5),
+                        retry_limit: Some(3),
+                        criteria: None,
                     }]),
-                    on_failure: None,
+                    outputs: None,
                 }],
+                outputs: None,
             }],
         };
-
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1544,10 +4371,9 @@ mod tests {
             "flowchart TD\n",
             "    subgraph workflowFoo\n",
             "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_doneNode{$response.body.status == 'approved'}\n",
-            "    workflowFoo_doneNode{$response.body.status == 'approved'} -->|true| workflowFooEndNode((End))\n",
-            "    workflowFoo_doneNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_stepFoo_retryFooRetryNode([\"Retry after 1.5s up to 3 times\"])\n",
+            "    workflowFoo_stepFoo_retryFooRetryNode([\"Retry after 1.5s up to 3 times\"]) --> workflowFoo_stepFoo\n",
             "    end\n",
         );
 
@@ -1556,123 +4382,96 @@ mod tests {
     }
 
     #[test]
-    fn render_on_action_on_success_only() {
+    fn render_options() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
-                steps: vec![
-                    Step {
-                        step_id: String::from("stepFoo"),
-                        description: None,
-                        success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
-                        on_success: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
-                            action_type: ActionType::Goto,
-                            workflow_id: None,
-                            step_id: Some(String::from("stepBar")),
-                            criteria: Some(vec![Criteria {
-                                condition: Some(String::from(
-                                    "$response.body.status == 'approved'",
-                                )),
-                            }]),
-                        }]),
-                        on_failure: None,
-                    },
-                    Step {
-                        step_id: String::from("stepBar"),
-                        description: None,
-                        success_criteria: None,
-                        on_success: None,
-                        on_failure: None,
-                    },
-                ],
+                inputs: None,
+                depends_on: None,
+                steps: vec![Step {
+                    step_id: String::from("stepFoo"),
+                    description: None,
+                    operation_id: None,
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
+                    success_criteria: Some(vec![Criteria {
+                        context: None,
+                        condition: Some(String::from("$statusCode == 200")),
+                        criteria_type: None,
+                    }]),
+                    on_success: None,
+                    on_failure: Some(vec![Action {
+                        name: String::from("retryFoo"),
+                        action_type: ActionType::Retry,
+                        workflow_id: None,
+                        step_id: None,
+                        retry_after: Some(1.5),
+                        retry_limit: Some(3),
+                        criteria: None,
+                    }]),
+                    outputs: None,
+                }],
+                outputs: None,
             }],
         };
-
-        let sut = MermaidFlowchart;
-
-        let actual = sut.render(&arazzo);
-
-        let expected = concat!(
-            "---\n",
-            "title: Workflows\n",
-            "---\n",
-            "flowchart TD\n",
-            "    subgraph workflowFoo\n",
-            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
-            "    end\n",
-        );
-
-        println!("{}", actual);
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn render_on_action_on_failure_only() {
-        let arazzo = ArazzoDocument {
-            info: Info {
-                title: String::from("Workflows"),
+        let sut = MermaidFlowchart {
+            workflow_filter: None,
+            options: FlowchartOptions {
+                direction: Direction::LR,
+                step_label: StepLabel::Description,
+                theme: Some(String::from("forest")),
+                theme_variables: Some(IndexMap::from([(
+                    String::from("primaryColor"),
+                    String::from("#ff6600"),
+                )])),
+                class_defs: Some(ClassDefs {
+                    step: String::from("fill:#fff"),
+                    ..ClassDefs::default()
+                }),
+                expand_external: false,
             },
-            workflows: vec![Workflow {
-                workflow_id: String::from("workflowFoo"),
-                description: None,
-                steps: vec![
-                    Step {
-                        step_id: String::from("stepFoo"),
-                        description: None,
-                        success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
-                        on_success: None,
-                        on_failure: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
-                            action_type: ActionType::Goto,
-                            workflow_id: None,
-                            step_id: Some(String::from("stepBar")),
-                            criteria: Some(vec![Criteria {
-                                condition: Some(String::from("$response.body.error != null")),
-                            }]),
-                        }]),
-                    },
-                    Step {
-                        step_id: String::from("stepBar"),
-                        description: None,
-                        success_criteria: None,
-                        on_success: None,
-                        on_failure: None,
-                    },
-                ],
-            }],
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::default(),
+            trace: None,
+            coverage: None,
+            diff: None,
         };
 
-        let sut = MermaidFlowchart;
-
         let actual = sut.render(&arazzo);
 
         let expected = concat!(
             "---\n",
             "title: Workflows\n",
             "---\n",
-            "flowchart TD\n",
+            "%%{init: {\"theme\":\"forest\",\"themeVariables\":{\"primaryColor\":\"#ff6600\"}}}%%\n",
+            "flowchart LR\n",
             "    subgraph workflowFoo\n",
             "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_proceedToStepBarNode{$response.body.error != null}\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.error != null} -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.error != null} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_stepFoo_retryFooRetryNode([\"Retry after 1.5s up to 3 times\"])\n",
+            "    workflowFoo_stepFoo_retryFooRetryNode([\"Retry after 1.5s up to 3 times\"]) --> workflowFoo_stepFoo\n",
             "    end\n",
+            "    classDef stepNode fill:#fff\n",
+            "    classDef decisionNode fill:#fef7e0,stroke:#f9ab00\n",
+            "    classDef endNode fill:#e6f4ea,stroke:#1e8e3e\n",
+            "    classDef crossWorkflowNode fill:#f3e8fd,stroke:#9334e6,stroke-dasharray:4 2\n",
+            "    classDef retryNode fill:#fce8e6,stroke:#d93025\n",
+            "    class workflowFoo_stepFoo stepNode\n",
+            "    class workflowFoo_stepFooNode decisionNode\n",
+            "    class workflowFooEndNode endNode\n",
+            "    class workflowFoo_stepFoo_retryFooRetryNode retryNode\n",
         );
 
         println!("{}", actual);
@@ -1680,42 +4479,68 @@ mod tests {
     }
 
     #[test]
-    fn render_on_action_with_empty_criteria() {
+    fn render_step_label_operation() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
-                        description: None,
-                        success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
-                        on_success: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
-                            action_type: ActionType::Goto,
-                            workflow_id: None,
-                            step_id: Some(String::from("stepBar")),
-                            criteria: Some(vec![]),
-                        }]),
+                        description: Some(String::from("Step foo's description.")),
+                        operation_id: None,
+                        operation_path: Some(String::from(
+                            "{$sourceDescriptions.fooApi.url}#/paths/~1foos/post",
+                        )),
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: Some(String::from("getBar")),
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart {
+            workflow_filter: None,
+            options: FlowchartOptions {
+                step_label: StepLabel::Operation,
+                ..FlowchartOptions::default()
+            },
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::default(),
+            trace: None,
+            coverage: None,
+            diff: None,
+        };
 
         let actual = sut.render(&arazzo);
 
@@ -1725,12 +4550,8 @@ mod tests {
             "---\n",
             "flowchart TD\n",
             "    subgraph workflowFoo\n",
-            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode\n",
-            "    workflowFoo_proceedToStepBarNode -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_proceedToStepBarNode -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFoo[\"POST /foos (fooApi)\"] --> workflowFoo_stepBar[\"getBar\"]\n",
+            "    workflowFoo_stepBar[\"getBar\"] --> workflowFooEndNode((End))\n",
             "    end\n",
         );
 
@@ -1739,49 +4560,68 @@ mod tests {
     }
 
     #[test]
-    fn render_on_action_with_multiple_action_criteria() {
+    fn render_step_label_template() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![Workflow {
                 workflow_id: String::from("workflowFoo"),
+                summary: None,
                 description: None,
+                inputs: None,
+                depends_on: None,
                 steps: vec![
                     Step {
                         step_id: String::from("stepFoo"),
-                        description: None,
+                        description: Some(String::from("Step foo's description.")),
+                        operation_id: None,
+                        operation_path: Some(String::from(
+                            "{$sourceDescriptions.fooApi.url}#/paths/~1foos/post",
+                        )),
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
-                        on_success: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
-                            action_type: ActionType::Goto,
-                            workflow_id: None,
-                            step_id: Some(String::from("stepBar")),
-                            criteria: Some(vec![
-                                Criteria {
-                                    condition: Some(String::from(
-                                        "$response.body.status == 'approved'",
-                                    )),
-                                },
-                                Criteria {
-                                    condition: Some(String::from("$response.body.error == null")),
-                                },
-                            ]),
-                        }]),
+                        on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                     Step {
                         step_id: String::from("stepBar"),
                         description: None,
+                        operation_id: Some(String::from("getBar")),
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     },
                 ],
+                outputs: None,
             }],
         };
 
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart {
+            workflow_filter: None,
+            options: FlowchartOptions {
+                step_label: StepLabel::from(String::from("{stepId}: {description} ({source})")),
+                ..FlowchartOptions::default()
+            },
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::default(),
+            trace: None,
+            coverage: None,
+            diff: None,
+        };
 
         let actual = sut.render(&arazzo);
 
@@ -1791,12 +4631,8 @@ mod tests {
             "---\n",
             "flowchart TD\n",
             "    subgraph workflowFoo\n",
-            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode\n",
-            "    workflowFoo_stepFooNode -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved' && $response.body.error == null}\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved' && $response.body.error == null} -->|true| workflowFoo_stepBar\n",
-            "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved' && $response.body.error == null} -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepFooNode -->|false| workflowFooEndNode((End))\n",
-            "    workflowFoo_stepBar --> workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFoo[\"stepFoo: Step foo's description. (fooApi)\"] --> workflowFoo_stepBar[\"stepBar: \"]\n",
+            "    workflowFoo_stepBar[\"stepBar: \"] --> workflowFooEndNode((End))\n",
             "    end\n",
         );
 
@@ -1805,61 +4641,83 @@ mod tests {
     }
 
     #[test]
-    fn render_on_action_goto_another_workflow() {
+    fn step_label_template_keeps_text() {
+        let step: Step = yaml_serde::from_str(concat!(
+            "stepId: stepFoo\n",
+            "description: Call foo() now\n",
+            "operationId: getFoo\n",
+        ))
+        .unwrap();
+        let flowchart = MermaidFlowchart::builder()
+            .step_label(StepLabel::from(String::from("{description}  |  {operationId} ({source})")))
+            .build();
+
+        let actual = step_label(&step, &flowchart);
+
+        assert_eq!(Some(String::from("Call foo() now  |  getFoo")), actual);
+    }
+
+    #[test]
+    fn render_step_invoking_workflow() {
         let arazzo = ArazzoDocument {
             info: Info {
                 title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
             },
+            source_descriptions: None,
+            components: None,
             workflows: vec![
                 Workflow {
                     workflow_id: String::from("workflowFoo"),
+                    summary: None,
                     description: None,
+                    inputs: None,
+                    depends_on: None,
                     steps: vec![Step {
                         step_id: String::from("stepFoo"),
                         description: None,
-                        success_criteria: None,
-                        on_success: Some(vec![Action {
-                            name: String::from("proceedToStepBar"),
-                            action_type: ActionType::Goto,
-                            workflow_id: Some(String::from("workflowBar")),
-                            step_id: None,
-                            criteria: None,
-                        }]),
-                        on_failure: Some(vec![Action {
-                            name: String::from("proceedToStepBaz"),
-                            action_type: ActionType::Goto,
-                            workflow_id: Some(String::from("workflowBaz")),
-                            step_id: None,
-                            criteria: None,
-                        }]),
-                    }],
-                },
-                Workflow {
-                    workflow_id: String::from("workflowBar"),
-                    description: None,
-                    steps: vec![Step {
-                        step_id: String::from("stepFoo"),
-                        description: None,
+                        operation_id: None,
+                        operation_path: None,
+                        workflow_id: Some(String::from("workflowBar")),
+                        parameters: Some(vec![StepParameter::Parameter(Parameter {
+                            name: String::from("barId"),
+                            location: None,
+                            value: serde_json::Value::String(String::from("$inputs.barId")),
+                        })]),
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     }],
+                    outputs: None,
                 },
                 Workflow {
-                    workflow_id: String::from("workflowBaz"),
+                    workflow_id: String::from("workflowBar"),
+                    summary: None,
                     description: None,
+                    inputs: None,
+                    depends_on: None,
                     steps: vec![Step {
-                        step_id: String::from("stepFoo"),
-                        description: None,
+                        step_id: String::from("stepBar"),
+                        description: Some(String::from("Step bar's description.")),
+                        operation_id: Some(String::from("getBar")),
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: None,
                         success_criteria: None,
                         on_success: None,
                         on_failure: None,
+                        outputs: None,
                     }],
+                    outputs: None,
                 },
             ],
         };
-
-        let sut = MermaidFlowchart;
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
@@ -1869,15 +4727,11 @@ mod tests {
             "---\n",
             "flowchart TD\n",
             "    subgraph workflowFoo\n",
-            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode\n",
-            "    workflowFoo_stepFooNode -->|true| workflowBar\n",
-            "    workflowFoo_stepFooNode -->|false| workflowBaz\n",
+            "    workflowFoo_stepFoo[[\"workflowBar\"]] --> workflowFooEndNode((End))\n",
             "    end\n",
+            "    workflowFoo_stepFoo -.->|\"barId = $inputs.barId\"| workflowBar\n",
             "    subgraph workflowBar\n",
-            "    workflowBar_stepFoo --> workflowBarEndNode((End))\n",
-            "    end\n",
-            "    subgraph workflowBaz\n",
-            "    workflowBaz_stepFoo --> workflowBazEndNode((End))\n",
+            "    workflowBar_stepBar[\"Step bar's description.\"] --> workflowBarEndNode((End))\n",
             "    end\n",
         );
 
@@ -1886,95 +4740,47 @@ mod tests {
     }
 
     #[test]
-    fn render_on_actions() {
-        let arazzo = ArazzoDocument {
-            info: Info {
-                title: String::from("Workflows"),
-            },
-            workflows: vec![Workflow {
-                workflow_id: String::from("workflowFoo"),
-                description: None,
-                steps: vec![
-                    Step {
-                        step_id: String::from("stepFoo"),
-                        description: None,
-                        success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
-                        on_success: Some(vec![
-                            Action {
-                                name: String::from("proceedToStepBar"),
-                                action_type: ActionType::Goto,
-                                workflow_id: None,
-                                step_id: Some(String::from("stepBar")),
-                                criteria: Some(vec![Criteria {
-                                    condition: Some(String::from(
-                                        "$response.body.status == 'approved'",
-                                    )),
-                                }]),
-                            },
-                            Action {
-                                name: String::from("proceedToStepBaz"),
-                                action_type: ActionType::Goto,
-                                workflow_id: None,
-                                step_id: Some(String::from("stepBaz")),
-                                criteria: Some(vec![Criteria {
-                                    condition: Some(String::from(
-                                        "$response.body.status == 'declined'",
-                                    )),
-                                }]),
-                            },
-                        ]),
-                        on_failure: Some(vec![Action {
-                            name: String::from("done"),
-                            action_type: ActionType::End,
-                            workflow_id: None,
-                            step_id: None,
-                            criteria: Some(vec![Criteria {
-                                condition: Some(String::from("$response.body.error != null")),
-                            }]),
-                        }]),
-                    },
-                    Step {
-                        step_id: String::from("stepBar"),
-                        description: None,
-                        success_criteria: None,
-                        on_success: None,
-                        on_failure: None,
-                    },
-                    Step {
-                        step_id: String::from("stepBaz"),
-                        description: None,
-                        success_criteria: None,
-                        on_success: None,
-                        on_failure: None,
-                    },
-                ],
-            }],
-        };
+    fn render_subroutine_object_parameter() {
+        let filter = serde_json::json!({"status": "available", "tags": ["a", "b"]});
+        let parameters = vec![format!("filter = {}", describe_value(&filter))];
+
+        let actual = to_workflow_from_subroutine(
+            "workflowFoo_stepFoo",
+            "workflowBar",
+            &parameters,
+            &mut Edges::default(),
+        );
+
+        let expected = concat!(
+            "    workflowFoo_stepFoo -.->",
+            "|\"filter = {#quot;status#quot;:#quot;available#quot;,#quot;tags#quot;:[#quot;a#quot;,#quot;b#quot;]}\"|",
+            " workflowBar\n",
+        );
 
-        let sut = MermaidFlowchart;
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_external_workflow_collapsed() {
+        let content = std::fs::read_to_string("fixtures/composition/main.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+
+        let sut = MermaidFlowchart::builder().build();
 
         let actual = sut.render(&arazzo);
 
         let expected = concat!(
-        "---\n",
-        "title: Workflows\n",
-        "---\n",
-        "flowchart TD\n",
-        "    subgraph workflowFoo\n",
-        "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-        "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'}\n",
-        "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|true| workflowFoo_stepBar\n",
-        "    workflowFoo_proceedToStepBarNode{$response.body.status == 'approved'} -->|false| workflowFoo_proceedToStepBazNode{$response.body.status == 'declined'}\n",
-        "    workflowFoo_proceedToStepBazNode{$response.body.status == 'declined'} -->|true| workflowFoo_stepBaz\n",
-        "    workflowFoo_proceedToStepBazNode{$response.body.status == 'declined'} -->|false| workflowFooEndNode((End))\n",
-        "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_doneNode{$response.body.error != null}\n",
-        "    workflowFoo_doneNode{$response.body.error != null} -->|true| workflowFooEndNode((End))\n",
-        "    workflowFoo_doneNode{$response.body.error != null} -->|false| workflowFooEndNode((End))\n",
-        "    workflowFoo_stepBar --> workflowFoo_stepBaz\n",
-        "    workflowFoo_stepBaz --> workflowFooEndNode((End))\n",
-        "    end\n",
+            "---\n",
+            "title: Checkout Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph checkout\n",
+            "    checkout_placeOrderStep[\"Place an order.\"] --> checkout_placeOrderStepNode{$statusCode == 401}\n",
+            "    checkout_placeOrderStepNode{$statusCode == 401} -->|true| sharedFlows_login\n",
+            "    checkout_placeOrderStepNode{$statusCode == 401} -->|false| checkoutEndNode((End))\n",
+            "    end\n",
+            "    sharedFlows_login([\"sharedFlows.login\"])\n",
         );
 
         println!("{}", actual);
@@ -1982,85 +4788,39 @@ mod tests {
     }
 
     #[test]
-    fn render_on_actions_with_empty_criteria() {
-        let arazzo = ArazzoDocument {
-            info: Info {
-                title: String::from("Workflows"),
+    fn render_external_workflow_expanded() {
+        let file = std::path::Path::new("fixtures/composition/main.yml");
+        let content = std::fs::read_to_string(file).unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+
+        let sut = MermaidFlowchart {
+            workflow_filter: None,
+            options: FlowchartOptions {
+                expand_external: true,
+                ..FlowchartOptions::default()
             },
-            workflows: vec![Workflow {
-                workflow_id: String::from("workflowFoo"),
-                description: None,
-                steps: vec![
-                    Step {
-                        step_id: String::from("stepFoo"),
-                        description: None,
-                        success_criteria: Some(vec![Criteria {
-                            condition: Some(String::from("$statusCode == 200")),
-                        }]),
-                        on_success: Some(vec![
-                            Action {
-                                name: String::from("proceedToStepBar"),
-                                action_type: ActionType::Goto,
-                                workflow_id: None,
-                                step_id: Some(String::from("stepBar")),
-                                criteria: Some(vec![]),
-                            },
-                            Action {
-                                name: String::from("proceedToStepBaz"),
-                                action_type: ActionType::Goto,
-                                workflow_id: None,
-                                step_id: Some(String::from("stepBaz")),
-                                criteria: Some(vec![]),
-                            },
-                        ]),
-                        on_failure: Some(vec![Action {
-                            name: String::from("done"),
-                            action_type: ActionType::End,
-                            workflow_id: None,
-                            step_id: None,
-                            criteria: Some(vec![]),
-                        }]),
-                    },
-                    Step {
-                        step_id: String::from("stepBar"),
-                        description: None,
-                        success_criteria: None,
-                        on_success: None,
-                        on_failure: None,
-                    },
-                    Step {
-                        step_id: String::from("stepBaz"),
-                        description: None,
-                        success_criteria: None,
-                        on_success: None,
-                        on_failure: None,
-                    },
-                ],
-            }],
+            sources: OpenApiSources::default(),
+            external_workflows: ArazzoSources::load(&arazzo, Some(file)).unwrap(),
+            trace: None,
+            coverage: None,
+            diff: None,
         };
 
-        let sut = MermaidFlowchart;
-
         let actual = sut.render(&arazzo);
 
         let expected = concat!(
-        "---\n",
-        "title: Workflows\n",
-        "---\n",
-        "flowchart TD\n",
-        "    subgraph workflowFoo\n",
-        "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
-        "    workflowFoo_stepFooNode{$statusCode == 200} -->|true| workflowFoo_proceedToStepBarNode\n",
-        "    workflowFoo_proceedToStepBarNode -->|true| workflowFoo_stepBar\n",
-        "    workflowFoo_proceedToStepBarNode -->|false| workflowFoo_proceedToStepBazNode\n",
-        "    workflowFoo_proceedToStepBazNode -->|true| workflowFoo_stepBaz\n",
-        "    workflowFoo_proceedToStepBazNode -->|false| workflowFooEndNode((End))\n",
-        "    workflowFoo_stepFooNode{$statusCode == 200} -->|false| workflowFoo_doneNode\n",
-        "    workflowFoo_doneNode -->|true| workflowFooEndNode((End))\n",
-        "    workflowFoo_doneNode -->|false| workflowFooEndNode((End))\n",
-        "    workflowFoo_stepBar --> workflowFoo_stepBaz\n",
-        "    workflowFoo_stepBaz --> workflowFooEndNode((End))\n",
-        "    end\n",
+            "---\n",
+            "title: Checkout Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph checkout\n",
+            "    checkout_placeOrderStep[\"Place an order.\"] --> checkout_placeOrderStepNode{$statusCode == 401}\n",
+            "    checkout_placeOrderStepNode{$statusCode == 401} -->|true| sharedFlows_login\n",
+            "    checkout_placeOrderStepNode{$statusCode == 401} -->|false| checkoutEndNode((End))\n",
+            "    end\n",
+            "    subgraph sharedFlows_login[\"Log in.\"]\n",
+            "    sharedFlows_login_loginStep[\"Log in with credentials.\"] --> sharedFlows_loginEndNode((End))\n",
+            "    end\n",
         );
 
         println!("{}", actual);
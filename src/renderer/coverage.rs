@@ -0,0 +1,434 @@
+use std::fmt::{self, Display};
+
+use serde::Deserialize;
+
+use crate::renderer::trace::TraceOutcome;
+use crate::renderer::{Edge, Graph, should_branch};
+
+const HEAT_STYLES: [&str; 5] = [
+    "fill:#f1f3f4,stroke:#d93025,stroke-dasharray:4 2",
+    "fill:#fff5eb,stroke:#fd8d3c",
+    "fill:#fdd0a2,stroke:#f16913",
+    "fill:#fd8d3c,stroke:#d94801",
+    "fill:#d94801,stroke:#7f2704,color:#ffffff",
+];
+const NEVER_STYLE: &str = "stroke:#d93025,stroke-dasharray:4 2";
+const NEVER: &str = "(never)";
+
+/// Aggregated execution counts, one per step or per step outcome and action.
+/// Rows without `outcome` count how often the step ran.
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Coverage {
+    pub counts: Vec<CoverageCount>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageCount {
+    pub workflow_id: Option<String>,
+    pub step_id: String,
+    pub outcome: Option<TraceOutcome>,
+    pub action: Option<String>,
+    pub count: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CoverageError(String);
+
+impl Display for CoverageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to parse coverage: {}", self.0)
+    }
+}
+
+impl std::error::Error for CoverageError {}
+
+/// How often a step ran and took each of its branches.
+pub struct StepCoverage<'a> {
+    counts: Vec<&'a CoverageCount>,
+    pub hits: u64,
+    success: u64,
+    failure: u64,
+}
+
+/// How often a branch was taken, out of the runs that reached its decision.
+#[derive(Clone, Copy)]
+pub struct Hits {
+    pub taken: u64,
+    pub reached: u64,
+}
+
+impl Hits {
+    pub fn never(&self) -> bool {
+        self.taken == 0 || self.reached == 0
+    }
+}
+
+impl Display for Hits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.never() {
+            write!(f, "0% {NEVER}")
+        } else {
+            write!(f, "{}%", (self.taken * 100 + self.reached / 2) / self.reached)
+        }
+    }
+}
+
+impl Coverage {
+    pub fn from_json(content: &str) -> Result<Coverage, CoverageError> {
+        serde_json::from_str(content).map_err(|error| CoverageError(error.to_string()))
+    }
+
+    /// Reads a CSV file with a `workflowId,stepId,outcome,action,count` header, in
+    /// any column order. Empty cells leave the field unset.
+    pub fn from_csv(content: &str) -> Result<Coverage, CoverageError> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let header = lines.next().unwrap_or_default();
+        let columns = header.split(',').map(str::trim).collect::<Vec<&str>>();
+        let column = |name: &str| columns.iter().position(|c| *c == name);
+        let (Some(step_column), Some(count_column)) = (column("stepId"), column("count")) else {
+            return Err(CoverageError(String::from("header needs stepId and count columns")));
+        };
+
+        let mut counts = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let cells = line.split(',').map(str::trim).collect::<Vec<&str>>();
+            let cell = |index: Option<usize>| {
+                index
+                    .and_then(|index| cells.get(index))
+                    .filter(|cell| !cell.is_empty())
+                    .map(|cell| cell.to_string())
+            };
+            let row = i + 2;
+
+            let step_id = cell(Some(step_column))
+                .ok_or_else(|| CoverageError(format!("line {row}: missing stepId")))?;
+            let count = cell(Some(count_column))
+                .and_then(|count| count.parse().ok())
+                .ok_or_else(|| CoverageError(format!("line {row}: count is not a number")))?;
+            let outcome = match cell(column("outcome")).as_deref() {
+                Some("success") => Some(TraceOutcome::Success),
+                Some("failure") => Some(TraceOutcome::Failure),
+                Some(outcome) => {
+                    let message = format!("line {row}: unknown outcome '{outcome}'");
+                    return Err(CoverageError(message));
+                }
+                None => None,
+            };
+
+            counts.push(CoverageCount {
+                workflow_id: cell(column("workflowId")),
+                step_id,
+                outcome,
+                action: cell(column("action")),
+                count,
+            });
+        }
+
+        Ok(Coverage { counts })
+    }
+
+    /// Sums the counts of a step. A missing step count is the sum of its outcomes,
+    /// and a missing outcome is whatever the other one leaves of the step count.
+    pub fn step(&self, workflow_id: &str, step_id: &str) -> StepCoverage<'_> {
+        let counts = self
+            .counts
+            .iter()
+            .filter(|c| c.workflow_id.as_deref().is_none_or(|id| id == workflow_id))
+            .filter(|c| c.step_id == step_id)
+            .collect::<Vec<&CoverageCount>>();
+
+        // Rows naming an action break their outcome down, so they only count when
+        // the outcome itself has no row.
+        let sum = |outcome: Option<TraceOutcome>| {
+            let total = |with_action: bool| {
+                let mut matching = counts
+                    .iter()
+                    .filter(|c| c.outcome == outcome && c.action.is_some() == with_action)
+                    .peekable();
+                matching.peek().is_some().then(|| matching.map(|c| c.count).sum::<u64>())
+            };
+            total(false).or_else(|| total(true))
+        };
+        let success = sum(Some(TraceOutcome::Success));
+        let failure = sum(Some(TraceOutcome::Failure));
+        let hits = sum(None).unwrap_or(success.unwrap_or(0) + failure.unwrap_or(0));
+
+        StepCoverage {
+            counts,
+            hits,
+            success: success.unwrap_or(hits.saturating_sub(failure.unwrap_or(0))),
+            failure: failure.unwrap_or(hits.saturating_sub(success.unwrap_or(0))),
+        }
+    }
+}
+
+impl StepCoverage<'_> {
+    pub fn outcome(&self, outcome: TraceOutcome) -> u64 {
+        match outcome {
+            TraceOutcome::Success => self.success,
+            TraceOutcome::Failure => self.failure,
+        }
+    }
+
+    /// How often the named action was taken, when the data counts it.
+    pub fn action(&self, outcome: TraceOutcome, name: &str) -> Option<u64> {
+        let mut matching = self
+            .counts
+            .iter()
+            .filter(|c| c.outcome == Some(outcome) && c.action.as_deref() == Some(name))
+            .peekable();
+
+        matching.peek().is_some().then(|| matching.map(|c| c.count).sum())
+    }
+}
+
+/// Colours step and decision nodes by how often the step ran, relative to the
+/// busiest step, and flags the branches that were never taken.
+pub(super) fn coverage_styles(coverage: &Coverage, graphs: &[Graph], edges: &[Edge]) -> String {
+    let mut heat: Vec<(String, u64, bool)> = Vec::new();
+    for (graph_id, _, workflow) in graphs {
+        for step in &workflow.steps {
+            let hits = coverage.step(&workflow.workflow_id, &step.step_id).hits;
+            heat.push((format!("{}_{}", graph_id, step.step_id), hits, should_branch(step)));
+        }
+    }
+    let max = heat.iter().map(|(_, hits, _)| *hits).max().unwrap_or(0);
+
+    let mut styles: String = HEAT_STYLES
+        .iter()
+        .enumerate()
+        .map(|(level, style)| format!("    classDef heat{level} {style}\n"))
+        .collect();
+    for level in 0..HEAT_STYLES.len() {
+        let node_names = heat
+            .iter()
+            .filter(|(_, hits, _)| heat_level(*hits, max) == level)
+            .flat_map(|(node_name, _, branches)| {
+                let decision = branches.then(|| format!("{node_name}Node"));
+                std::iter::once(node_name.clone()).chain(decision)
+            })
+            .collect::<Vec<String>>();
+        if !node_names.is_empty() {
+            styles.push_str(&format!("    class {} heat{level}\n", node_names.join(",")));
+        }
+    }
+
+    let never = edges
+        .iter()
+        .enumerate()
+        .filter(|(_, edge)| edge.branch.and_then(|b| b.hits).is_some_and(|hits| hits.never()))
+        .map(|(index, _)| index.to_string())
+        .collect::<Vec<String>>();
+    if !never.is_empty() {
+        styles.push_str(&format!("    linkStyle {} {NEVER_STYLE}\n", never.join(",")));
+    }
+
+    styles
+}
+
+/// `0` for steps that never ran, then `1` to `4` by share of the busiest step.
+fn heat_level(hits: u64, max: u64) -> usize {
+    if hits == 0 || max == 0 {
+        0
+    } else {
+        (hits * 4).div_ceil(max) as usize
+    }
+}
+
+/// Counts the branches drawn in each workflow subgraph and lists those never taken.
+pub(super) fn coverage_summary(graphs: &[Graph], edges: &[Edge]) -> String {
+    let mut summary = String::new();
+    for (graph_id, _, _) in graphs {
+        let mut never = Vec::new();
+        let mut branches = 0;
+        for edge in edges.iter().filter(|edge| edge.graph_id.as_ref() == Some(graph_id)) {
+            let Some((verdict, hits)) = edge.branch.and_then(|b| b.hits.map(|h| (b.verdict, h)))
+            else {
+                continue;
+            };
+            branches += 1;
+            if hits.never() {
+                never.push(format!("{} -->|{} {}| {}", edge.from, verdict.label(), hits, edge.to));
+            }
+        }
+        summary.push_str(&describe_coverage(graph_id, &never, branches));
+    }
+
+    summary
+}
+
+fn describe_coverage(graph_id: &str, never: &[String], branches: usize) -> String {
+    let covered = branches - never.len();
+    let percentage = (covered * 100).checked_div(branches).unwrap_or(100);
+    let mut description =
+        format!("{graph_id}: {covered}/{branches} branches covered ({percentage}%)\n");
+    for branch in never {
+        description.push_str(&format!("  never taken: {branch}\n"));
+    }
+
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::ArazzoDocument;
+    use crate::renderer::{MermaidFlowchart, Renderer};
+
+    #[test]
+    fn parse_coverage_csv() {
+        let content = concat!(
+            "stepId,outcome,count,action\n",
+            "stepFoo,,12,\n",
+            "stepFoo,failure,2,retryFoo\n",
+        );
+
+        let actual = Coverage::from_csv(content).unwrap().counts;
+
+        let expected = vec![
+            CoverageCount {
+                workflow_id: None,
+                step_id: String::from("stepFoo"),
+                outcome: None,
+                action: None,
+                count: 12,
+            },
+            CoverageCount {
+                workflow_id: None,
+                step_id: String::from("stepFoo"),
+                outcome: Some(TraceOutcome::Failure),
+                action: Some(String::from("retryFoo")),
+                count: 2,
+            },
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_invalid_coverage_csv() {
+        let actual = [
+            Coverage::from_csv("stepId,outcome\nstepFoo,success\n").unwrap_err(),
+            Coverage::from_csv("stepId,count\nstepFoo,many\n").unwrap_err(),
+            Coverage::from_csv("stepId,outcome,count\nstepFoo,skipped,1\n").unwrap_err(),
+        ]
+        .map(|error| error.to_string());
+
+        let expected = [
+            "Failed to parse coverage: header needs stepId and count columns",
+            "Failed to parse coverage: line 2: count is not a number",
+            "Failed to parse coverage: line 2: unknown outcome 'skipped'",
+        ];
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_coverage() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let coverage = Coverage::from_json(concat!(
+            "[",
+            "{\"stepId\": \"createAuthorizationStep\", \"outcome\": \"success\", \"count\": 90},",
+            "{\"stepId\": \"createAuthorizationStep\", \"outcome\": \"failure\", \"count\": 10},",
+            "{\"stepId\": \"captureAuthorizationStep\", \"outcome\": \"success\", \"count\": 90},",
+            "{\"stepId\": \"reverseAuthorizationStep\", \"outcome\": \"success\", \"count\": 10}",
+            "]",
+        ))
+        .unwrap();
+        let sut = MermaidFlowchart::builder().coverage(coverage).build();
+
+        let (actual, summary) = sut.render_with_coverage_summary(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Credit Card API Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph authorizeThenCapture[\"Create a card authorization and then capture it.\"]\n",
+            "    authorizeThenCapture_createAuthorizationStep[\"Create a card authorization.\"] --> authorizeThenCapture_createAuthorizationStepNode{$statusCode == 201}\n",
+            "    authorizeThenCapture_createAuthorizationStepNode{$statusCode == 201} -->|\"true 90%\"| authorizeThenCapture_captureAuthorizationStep\n",
+            "    authorizeThenCapture_createAuthorizationStepNode{$statusCode == 201} -->|\"false 10%\"| authorizeThenCapture_reverseAuthorizationStep\n",
+            "    authorizeThenCapture_captureAuthorizationStep[\"Capture a card authorization.\"] --> authorizeThenCapture_captureAuthorizationStepNode{$statusCode == 201}\n",
+            "    authorizeThenCapture_captureAuthorizationStepNode{$statusCode == 201} -->|\"true 100%\"| authorizeThenCaptureEndNode((End))\n",
+            "    authorizeThenCapture_captureAuthorizationStepNode{$statusCode == 201} -->|\"false 0% (never)\"| authorizeThenCapture_reverseAuthorizationStep\n",
+            "    authorizeThenCapture_reverseAuthorizationStep[\"Reverse a card authorization.\"] --> authorizeThenCapture_reverseAuthorizationStepNode{$statusCode == 201}\n",
+            "    authorizeThenCapture_reverseAuthorizationStepNode{$statusCode == 201} -->|\"true 100%\"| authorizeThenCaptureEndNode((End))\n",
+            "    authorizeThenCapture_reverseAuthorizationStepNode{$statusCode == 201} -->|\"false 0% (never)\"| authorizeThenCaptureEndNode((End))\n",
+            "    end\n",
+            "    classDef heat0 fill:#f1f3f4,stroke:#d93025,stroke-dasharray:4 2\n",
+            "    classDef heat1 fill:#fff5eb,stroke:#fd8d3c\n",
+            "    classDef heat2 fill:#fdd0a2,stroke:#f16913\n",
+            "    classDef heat3 fill:#fd8d3c,stroke:#d94801\n",
+            "    classDef heat4 fill:#d94801,stroke:#7f2704,color:#ffffff\n",
+            "    class authorizeThenCapture_reverseAuthorizationStep,authorizeThenCapture_reverseAuthorizationStepNode heat1\n",
+            "    class authorizeThenCapture_createAuthorizationStep,authorizeThenCapture_createAuthorizationStepNode,authorizeThenCapture_captureAuthorizationStep,authorizeThenCapture_captureAuthorizationStepNode heat4\n",
+            "    linkStyle 5,8 stroke:#d93025,stroke-dasharray:4 2\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+
+        let actual = summary;
+
+        let expected = concat!(
+            "authorizeThenCapture: 4/6 branches covered (66%)\n",
+            "  never taken: authorizeThenCapture_captureAuthorizationStepNode -->|false 0% (never)| authorizeThenCapture_reverseAuthorizationStep\n",
+            "  never taken: authorizeThenCapture_reverseAuthorizationStepNode -->|false 0% (never)| authorizeThenCaptureEndNode\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_action_coverage() {
+        let arazzo: ArazzoDocument = yaml_serde::from_str(concat!(
+            "info:\n",
+            "  title: Workflows\n",
+            "workflows:\n",
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 200\n",
+            "        onFailure:\n",
+            "          - name: retryFoo\n",
+            "            type: retry\n",
+            "            criteria:\n",
+            "              - condition: $statusCode == 503\n",
+            "          - name: endFoo\n",
+            "            type: end\n",
+            "            criteria:\n",
+            "              - condition: $statusCode == 400\n",
+        ))
+        .unwrap();
+        let coverage = Coverage::from_csv(concat!(
+            "stepId,outcome,action,count\n",
+            "stepFoo,,,10\n",
+            "stepFoo,failure,,4\n",
+            "stepFoo,failure,retryFoo,3\n",
+        ))
+        .unwrap();
+        let sut = MermaidFlowchart::builder().coverage(coverage).build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 200}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|\"true 60%\"| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 200} -->|\"false 40%\"| workflowFoo_retryFooNode{$statusCode == 503}\n",
+            "    workflowFoo_retryFooNode{$statusCode == 503} -->|\"true 75%\"| workflowFoo_stepFoo_retryFooRetryNode([\"Retry\"])\n",
+            "    workflowFoo_stepFoo_retryFooRetryNode([\"Retry\"]) --> workflowFoo_stepFoo\n",
+            "    workflowFoo_retryFooNode{$statusCode == 503} -->|\"false 25%\"| workflowFoo_endFooNode{$statusCode == 400}\n",
+            "    workflowFoo_endFooNode{$statusCode == 400} -->|\"true 0% (never)\"| workflowFooEndNode((End))\n",
+            "    workflowFoo_endFooNode{$statusCode == 400} -->|\"false 100%\"| workflowFooEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert!(actual.contains(expected));
+    }
+}
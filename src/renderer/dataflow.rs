@@ -0,0 +1,337 @@
+use crate::arazzo::{ArazzoDocument, CriteriaTypeName, Step, Workflow};
+use crate::condition::Condition;
+use crate::expression::{self, Expression, WorkflowProperty};
+use crate::renderer::{MermaidFlowchart, Renderer, init, subgraph, title};
+
+/// Renders how data moves between workflow inputs, step outputs and workflow
+/// outputs, following the runtime expressions that reference them.
+pub struct DataFlowDiagram {
+    pub flowchart: MermaidFlowchart,
+}
+
+impl Renderer for DataFlowDiagram {
+    fn render(&self, arazzo: &ArazzoDocument) -> String {
+        let options = &self.flowchart.options;
+        let mut output = title(&arazzo.info.title);
+        output.push_str(&init(options));
+        output.push_str(&format!("flowchart {}\n", options.direction));
+
+        for workflow in self.flowchart.selected_workflows(arazzo) {
+            output.push_str(&render_workflow(arazzo, workflow));
+        }
+
+        output
+    }
+}
+
+/// A value a runtime expression reads.
+#[derive(Debug, PartialEq)]
+enum Source {
+    /// `$inputs.<name>`
+    Input(String),
+    /// `$steps.<stepId>.outputs.<name>`
+    StepOutput(String, String),
+    /// `$workflows.<workflowId>.outputs.<name>`
+    WorkflowOutput(String, String),
+}
+
+fn render_workflow(arazzo: &ArazzoDocument, workflow: &Workflow) -> String {
+    let graph_id = &workflow.workflow_id;
+    let mut output = subgraph(graph_id, workflow.description.as_deref());
+
+    let mut inputs = input_names(workflow);
+    let mut edges: Vec<String> = Vec::new();
+    let mut push_edge = |edge: String| {
+        if !edges.contains(&edge) {
+            edges.push(edge);
+        }
+    };
+
+    for step in &workflow.steps {
+        for (source, consumer) in step_sources(arazzo, step) {
+            if let Source::Input(name) = &source
+                && !inputs.contains(name)
+            {
+                inputs.push(name.clone());
+            }
+            push_edge(edge(
+                graph_id,
+                &source,
+                &format!("{}_{}", graph_id, step.step_id),
+                &consumer,
+            ));
+        }
+    }
+    for (name, value) in workflow.outputs.iter().flatten() {
+        for source in text_sources(value) {
+            if let Source::Input(name) = &source
+                && !inputs.contains(name)
+            {
+                inputs.push(name.clone());
+            }
+            push_edge(edge(graph_id, &source, &format!("{graph_id}_output_{name}"), name));
+        }
+    }
+
+    for name in &inputs {
+        output.push_str(&format!("    {graph_id}_input_{name}[/\"{name}\"/]\n"));
+    }
+    for step in &workflow.steps {
+        output.push_str(&format!(
+            "    {graph_id}_{step_id}[\"{step_id}\"]\n",
+            step_id = step.step_id,
+        ));
+    }
+    for name in workflow.outputs.iter().flat_map(|outputs| outputs.keys()) {
+        output.push_str(&format!("    {graph_id}_output_{name}[\\\"{name}\"\\]\n"));
+    }
+    output.push_str("    end\n");
+
+    for edge in edges {
+        output.push_str(&edge);
+    }
+
+    output
+}
+
+/// Names of the inputs declared in the workflow's JSON schema.
+fn input_names(workflow: &Workflow) -> Vec<String> {
+    workflow
+        .inputs
+        .as_ref()
+        .and_then(|inputs| inputs.get("properties"))
+        .and_then(|properties| properties.as_object())
+        .map_or(Vec::new(), |properties| properties.keys().cloned().collect())
+}
+
+/// Every value a step reads, paired with what reads it: a parameter name,
+/// `requestBody`, `criteria` or the name of one of the step's outputs.
+fn step_sources(arazzo: &ArazzoDocument, step: &Step) -> Vec<(Source, String)> {
+    let mut step_sources = Vec::new();
+
+    for parameter in step.parameters.iter().flatten() {
+        let Some(parameter) = arazzo.resolve_parameter(parameter) else {
+            continue;
+        };
+        for source in value_sources(&parameter.value) {
+            step_sources.push((source, parameter.name.clone()));
+        }
+    }
+
+    if let Some(payload) = step.request_body.as_ref().and_then(|b| b.payload.as_ref()) {
+        for source in value_sources(payload) {
+            step_sources.push((source, String::from("requestBody")));
+        }
+    }
+
+    for criterion in expression::step_criteria(step) {
+        if let Some(context) = criterion.context.as_deref() {
+            for source in text_sources(context) {
+                step_sources.push((source, String::from("criteria")));
+            }
+        }
+        if let Some(condition) = criterion.condition.as_deref()
+            && expression::criteria_type_name(criterion) == CriteriaTypeName::Simple
+            && let Ok(condition) = condition.parse::<Condition>()
+        {
+            for source in sources(condition.expressions().into_iter().cloned()) {
+                step_sources.push((source, String::from("criteria")));
+            }
+        }
+    }
+
+    for (name, value) in step.outputs.iter().flatten() {
+        for source in text_sources(value) {
+            step_sources.push((source, name.clone()));
+        }
+    }
+
+    step_sources
+}
+
+fn value_sources(value: &serde_json::Value) -> Vec<Source> {
+    match value {
+        serde_json::Value::String(text) => text_sources(text),
+        serde_json::Value::Array(values) => values.iter().flat_map(value_sources).collect(),
+        serde_json::Value::Object(values) => values.values().flat_map(value_sources).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn text_sources(text: &str) -> Vec<Source> {
+    sources(expression::parse_value(text).unwrap_or_default())
+}
+
+/// Keeps the expressions that read workflow inputs, step outputs or workflow outputs.
+fn sources(expressions: impl IntoIterator<Item = Expression>) -> Vec<Source> {
+    expressions
+        .into_iter()
+        .filter_map(|expression| match expression {
+            Expression::Inputs(reference) => Some(Source::Input(root(reference.name))),
+            Expression::Steps {
+                step_id,
+                outputs: Some(reference),
+            } => Some(Source::StepOutput(step_id, root(reference.name))),
+            Expression::Workflows {
+                workflow_id,
+                property: Some(WorkflowProperty::Outputs(reference)),
+            } => Some(Source::WorkflowOutput(workflow_id, root(reference.name))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The name of the value an input or output reference reads, without nested fields.
+fn root(name: String) -> String {
+    match name.split_once('.') {
+        Some((root, _)) => root.to_string(),
+        None => name,
+    }
+}
+
+fn edge(graph_id: &str, source: &Source, to_node_name: &str, consumer: &str) -> String {
+    let (from_node_name, produced) = match source {
+        Source::Input(name) => (format!("{graph_id}_input_{name}"), name),
+        Source::StepOutput(step_id, name) => (format!("{graph_id}_{step_id}"), name),
+        Source::WorkflowOutput(workflow_id, name) => {
+            (format!("{workflow_id}_output_{name}"), name)
+        }
+    };
+
+    let label = if produced == consumer {
+        produced.to_string()
+    } else {
+        format!("{produced} → {consumer}")
+    };
+
+    format!("    {from_node_name} -->|\"{label}\"| {to_node_name}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::{Criteria, Info, Parameter, RequestBody, StepParameter};
+    use indexmap::IndexMap;
+
+    #[test]
+    fn render_data_flow() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: None,
+                inputs: Some(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "fooId": { "type": "string" },
+                    },
+                })),
+                depends_on: None,
+                steps: vec![
+                    Step {
+                        step_id: String::from("stepFoo"),
+                        description: None,
+                        operation_id: Some(String::from("getFoo")),
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: Some(vec![StepParameter::Parameter(Parameter {
+                            name: String::from("id"),
+                            location: Some(String::from("path")),
+                            value: serde_json::Value::String(String::from("$inputs.fooId")),
+                        })]),
+                        request_body: None,
+                        success_criteria: None,
+                        on_success: None,
+                        on_failure: None,
+                        outputs: Some(IndexMap::from([(
+                            String::from("barId"),
+                            String::from("$response.body#/barId"),
+                        )])),
+                    },
+                    Step {
+                        step_id: String::from("stepBar"),
+                        description: None,
+                        operation_id: Some(String::from("createBar")),
+                        operation_path: None,
+                        workflow_id: None,
+                        parameters: None,
+                        request_body: Some(RequestBody {
+                            content_type: None,
+                            payload: Some(serde_json::json!({
+                                "barId": "$steps.stepFoo.outputs.barId",
+                                "note": "{$inputs.note}",
+                            })),
+                        }),
+                        success_criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$response.body#/barId == $steps.stepFoo.outputs.barId")),
+                            criteria_type: None,
+                        }]),
+                        on_success: None,
+                        on_failure: None,
+                        outputs: Some(IndexMap::from([(
+                            String::from("receipt"),
+                            String::from("$response.body#/receipt"),
+                        )])),
+                    },
+                ],
+                outputs: Some(IndexMap::from([(
+                    String::from("receipt"),
+                    String::from("$steps.stepBar.outputs.receipt"),
+                )])),
+            }],
+        };
+        let sut = DataFlowDiagram {
+            flowchart: MermaidFlowchart::builder().build(),
+        };
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_input_fooId[/\"fooId\"/]\n",
+            "    workflowFoo_input_note[/\"note\"/]\n",
+            "    workflowFoo_stepFoo[\"stepFoo\"]\n",
+            "    workflowFoo_stepBar[\"stepBar\"]\n",
+            "    workflowFoo_output_receipt[\\\"receipt\"\\]\n",
+            "    end\n",
+            "    workflowFoo_input_fooId -->|\"fooId → id\"| workflowFoo_stepFoo\n",
+            "    workflowFoo_stepFoo -->|\"barId → requestBody\"| workflowFoo_stepBar\n",
+            "    workflowFoo_input_note -->|\"note → requestBody\"| workflowFoo_stepBar\n",
+            "    workflowFoo_stepFoo -->|\"barId → criteria\"| workflowFoo_stepBar\n",
+            "    workflowFoo_stepBar -->|\"receipt\"| workflowFoo_output_receipt\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn sources_in_expressions() {
+        let condition = "$steps.stepFoo.outputs.fooId == $inputs.barId && $workflows.wf.outputs.x == $statusCode"
+            .parse::<Condition>()
+            .unwrap();
+
+        let actual = sources(condition.expressions().into_iter().cloned());
+
+        let expected = vec![
+            Source::StepOutput(String::from("stepFoo"), String::from("fooId")),
+            Source::Input(String::from("barId")),
+            Source::WorkflowOutput(String::from("wf"), String::from("x")),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+}
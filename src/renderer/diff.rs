@@ -0,0 +1,504 @@
+use serde::Serialize;
+
+use crate::arazzo::{Action, ActionType, ArazzoDocument, Step, Workflow};
+use crate::renderer::{Graph, describe_action, describe_criteria, describe_operation, should_branch};
+
+const ADDED_STYLE: &str = "fill:#e6f4ea,stroke:#1e8e3e,stroke-width:2px";
+const REMOVED_STYLE: &str = "fill:#fce8e6,stroke:#d93025,stroke-width:2px,stroke-dasharray:4 2";
+const CHANGED_STYLE: &str = "fill:#fef7e0,stroke:#f9ab00,stroke-width:2px";
+
+/// How the control flow changed between two versions of a document.
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct Diff {
+    pub changes: Vec<Change>,
+}
+
+/// A workflow, a step, or one of a step's elements that was added, removed or
+/// changed. `element` is `operation`, `successCriteria`, `order` (the step that
+/// follows it), or `onSuccess.<name>` and `onFailure.<name>` for actions; it is
+/// omitted for whole workflows and steps.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub workflow_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    const ALL: [ChangeKind; 3] = [ChangeKind::Added, ChangeKind::Removed, ChangeKind::Changed];
+
+    fn class_name(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        }
+    }
+
+    fn style(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => ADDED_STYLE,
+            ChangeKind::Removed => REMOVED_STYLE,
+            ChangeKind::Changed => CHANGED_STYLE,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        }
+    }
+}
+
+impl Diff {
+    /// Matches workflows by `workflowId`, steps by `stepId` and actions by `name`.
+    pub fn compare(old: &ArazzoDocument, new: &ArazzoDocument) -> Diff {
+        let mut diff = Diff::default();
+
+        for workflow in &new.workflows {
+            match old.workflows.iter().find(|w| w.workflow_id == workflow.workflow_id) {
+                Some(old_workflow) => diff.compare_workflow(old_workflow, workflow),
+                None => diff.push(ChangeKind::Added, workflow, None),
+            }
+        }
+        for workflow in &old.workflows {
+            if !new.workflows.iter().any(|w| w.workflow_id == workflow.workflow_id) {
+                diff.push(ChangeKind::Removed, workflow, None);
+            }
+        }
+
+        diff
+    }
+
+    fn push(&mut self, kind: ChangeKind, workflow: &Workflow, step: Option<&Step>) {
+        self.changes.push(Change {
+            kind,
+            workflow_id: workflow.workflow_id.clone(),
+            step_id: step.map(|step| step.step_id.clone()),
+            element: None,
+            old: None,
+            new: None,
+        });
+    }
+
+    fn compare_workflow(&mut self, old: &Workflow, new: &Workflow) {
+        let old_order = kept_steps(old, new);
+        let new_order = kept_steps(new, old);
+
+        for step in &new.steps {
+            match old.steps.iter().find(|s| s.step_id == step.step_id) {
+                Some(old_step) => {
+                    self.compare_step(new, old_step, step);
+                    let before = following_step(&old_order, &step.step_id);
+                    let after = following_step(&new_order, &step.step_id);
+                    self.compare_element(new, step, String::from("order"), before, after);
+                }
+                None => self.push(ChangeKind::Added, new, Some(step)),
+            }
+        }
+        for step in &old.steps {
+            if !new.steps.iter().any(|s| s.step_id == step.step_id) {
+                self.push(ChangeKind::Removed, new, Some(step));
+            }
+        }
+    }
+
+    fn compare_step(&mut self, workflow: &Workflow, old: &Step, new: &Step) {
+        let operation = |step| describe_operation(step).map(str::to_string);
+        let criteria = |step: &Step| step.success_criteria.as_deref().map(describe_criteria);
+        let elements = [
+            ("operation", operation(old), operation(new)),
+            ("successCriteria", criteria(old), criteria(new)),
+        ];
+        for (element, before, after) in elements {
+            self.compare_element(workflow, new, element.to_string(), before, after);
+        }
+
+        let sides = [
+            ("onSuccess", &old.on_success, &new.on_success),
+            ("onFailure", &old.on_failure, &new.on_failure),
+        ];
+        for (side, before, after) in sides {
+            for action in after.iter().flatten() {
+                let previous = before.iter().flatten().find(|a| a.name == action.name);
+                let element = format!("{side}.{}", action.name);
+                let after = Some(describe_action(action));
+                self.compare_element(workflow, new, element, previous.map(describe_action), after);
+            }
+            for action in before.iter().flatten() {
+                if !after.iter().flatten().any(|a| a.name == action.name) {
+                    let element = format!("{side}.{}", action.name);
+                    let before = Some(describe_action(action));
+                    self.compare_element(workflow, new, element, before, None);
+                }
+            }
+        }
+    }
+
+    fn compare_element(
+        &mut self,
+        workflow: &Workflow,
+        step: &Step,
+        element: String,
+        old: Option<String>,
+        new: Option<String>,
+    ) {
+        let kind = match (&old, &new) {
+            (None, Some(_)) => ChangeKind::Added,
+            (Some(_), None) => ChangeKind::Removed,
+            (Some(old), Some(new)) if old != new => ChangeKind::Changed,
+            _ => return,
+        };
+
+        self.changes.push(Change {
+            kind,
+            workflow_id: workflow.workflow_id.clone(),
+            step_id: Some(step.step_id.clone()),
+            element: Some(element),
+            old,
+            new,
+        });
+    }
+}
+
+/// The IDs of the workflow's steps that the other version has too, in order, so
+/// added and removed steps do not count as reordering.
+fn kept_steps<'a>(workflow: &'a Workflow, other: &Workflow) -> Vec<&'a str> {
+    workflow
+        .steps
+        .iter()
+        .map(|step| step.step_id.as_str())
+        .filter(|step_id| other.steps.iter().any(|s| s.step_id == *step_id))
+        .collect()
+}
+
+/// The step that implicitly runs after `step_id`, or the end of the workflow.
+fn following_step(order: &[&str], step_id: &str) -> Option<String> {
+    let index = order.iter().position(|s| *s == step_id)?;
+    let next = order.get(index + 1).map_or("end", |next| next);
+
+    Some(format!("then {next}"))
+}
+
+/// The new document with the removed workflows, steps and actions of the old one
+/// put back, so a single flowchart can show both versions. Removed steps follow
+/// the step they came after in the old document.
+pub fn merge(old: ArazzoDocument, mut new: ArazzoDocument) -> ArazzoDocument {
+    for old_workflow in old.workflows {
+        let Some(workflow) =
+            new.workflows.iter_mut().find(|w| w.workflow_id == old_workflow.workflow_id)
+        else {
+            new.workflows.push(old_workflow);
+            continue;
+        };
+
+        let mut position = 0;
+        for mut old_step in old_workflow.steps {
+            match workflow.steps.iter().position(|s| s.step_id == old_step.step_id) {
+                Some(index) => {
+                    let step = &mut workflow.steps[index];
+                    merge_actions(&mut step.on_success, old_step.on_success.take());
+                    merge_actions(&mut step.on_failure, old_step.on_failure.take());
+                    position = index + 1;
+                }
+                None => {
+                    workflow.steps.insert(position, old_step);
+                    position += 1;
+                }
+            }
+        }
+    }
+
+    new
+}
+
+fn merge_actions(actions: &mut Option<Vec<Action>>, old: Option<Vec<Action>>) {
+    for action in old.into_iter().flatten() {
+        if !actions.iter().flatten().any(|a| a.name == action.name) {
+            actions.get_or_insert_with(Vec::new).push(action);
+        }
+    }
+}
+
+/// Classes colouring added elements green, removed ones red and changed ones
+/// amber. A step whose elements changed is amber, while its decision, action
+/// and retry nodes take the colour of their own change.
+pub fn diff_styles(diff: &Diff, graphs: &[Graph]) -> String {
+    let mut classes: Vec<(String, ChangeKind)> = Vec::new();
+    let mut push = |node_name: String, kind: ChangeKind| {
+        if !classes.iter().any(|(n, _)| n == &node_name) {
+            classes.push((node_name, kind));
+        }
+    };
+
+    for change in &diff.changes {
+        for (graph_id, _, workflow) in graphs {
+            if workflow.workflow_id != change.workflow_id {
+                continue;
+            }
+            if change.step_id.is_none() {
+                push(graph_id.clone(), change.kind);
+            }
+
+            for step in &workflow.steps {
+                if change.step_id.as_ref().is_some_and(|step_id| step_id != &step.step_id) {
+                    continue;
+                }
+                let step_node = format!("{}_{}", graph_id, step.step_id);
+
+                match change.element.as_deref() {
+                    None => {
+                        push(step_node.clone(), change.kind);
+                        if should_branch(step) {
+                            push(format!("{step_node}Node"), change.kind);
+                        }
+                        let actions = step.on_success.iter().chain(&step.on_failure).flatten();
+                        for node_name in actions.flat_map(|a| action_nodes(graph_id, step, a)) {
+                            push(node_name, change.kind);
+                        }
+                    }
+                    Some("operation") => push(step_node, ChangeKind::Changed),
+                    Some("successCriteria") => {
+                        push(step_node.clone(), ChangeKind::Changed);
+                        push(format!("{step_node}Node"), change.kind);
+                    }
+                    Some(element) => {
+                        push(step_node, ChangeKind::Changed);
+                        if let Some(action) = find_action(step, element) {
+                            for node_name in action_nodes(graph_id, step, action) {
+                                push(node_name, change.kind);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut styles: String = ChangeKind::ALL
+        .iter()
+        .map(|kind| format!("    classDef {} {}\n", kind.class_name(), kind.style()))
+        .collect();
+    for kind in ChangeKind::ALL {
+        let node_names = classes
+            .iter()
+            .filter(|(_, k)| *k == kind)
+            .map(|(n, _)| n.as_str())
+            .collect::<Vec<&str>>();
+        if !node_names.is_empty() {
+            styles.push_str(&format!("    class {} {}\n", node_names.join(","), kind.class_name()));
+        }
+    }
+
+    styles
+}
+
+/// Finds the action an `onSuccess.<name>` or `onFailure.<name>` element names.
+fn find_action<'a>(step: &'a Step, element: &str) -> Option<&'a Action> {
+    let (side, name) = element.split_once('.')?;
+    let actions = match side {
+        "onSuccess" => &step.on_success,
+        "onFailure" => &step.on_failure,
+        _ => return None,
+    };
+
+    actions.iter().flatten().find(|action| action.name == name)
+}
+
+/// The decision node of an action with criteria and the node of a retry action.
+fn action_nodes(graph_id: &str, step: &Step, action: &Action) -> Vec<String> {
+    let mut node_names = Vec::new();
+    if action.criteria.is_some() {
+        node_names.push(format!("{}_{}Node", graph_id, action.name));
+    }
+    if action.action_type == ActionType::Retry {
+        node_names.push(format!("{}_{}_{}RetryNode", graph_id, step.step_id, action.name));
+    }
+
+    node_names
+}
+
+/// One line per change: `+` added, `-` removed, `~` changed.
+pub fn to_text(diff: &Diff) -> String {
+    if diff.changes.is_empty() {
+        return String::from("No changes\n");
+    }
+
+    let mut output = String::new();
+    for change in &diff.changes {
+        let mut line = format!("{} {}", change.kind.symbol(), change.workflow_id);
+        if let Some(step_id) = &change.step_id {
+            line.push_str(&format!(".{step_id}"));
+        }
+        if let Some(element) = &change.element {
+            line.push_str(&format!(" {element}"));
+        }
+        match (&change.old, &change.new) {
+            (Some(old), Some(new)) => line.push_str(&format!(": {old} -> {new}")),
+            (Some(value), None) | (None, Some(value)) => line.push_str(&format!(": {value}")),
+            (None, None) => {}
+        }
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{MermaidFlowchart, Renderer};
+
+    const OLD: &str = concat!(
+        "arazzo: 1.0.1\n",
+        "info:\n",
+        "  title: Workflows\n",
+        "  version: 1.0.0\n",
+        "workflows:\n",
+        "  - workflowId: workflowFoo\n",
+        "    steps:\n",
+        "      - stepId: stepFoo\n",
+        "        operationId: getFoo\n",
+        "        successCriteria:\n",
+        "          - condition: $statusCode == 200\n",
+        "      - stepId: stepBar\n",
+        "        operationId: getBar\n",
+        "  - workflowId: workflowBar\n",
+        "    steps:\n",
+        "      - stepId: stepBaz\n",
+        "        operationId: getBaz\n",
+    );
+
+    const NEW: &str = concat!(
+        "arazzo: 1.0.1\n",
+        "info:\n",
+        "  title: Workflows\n",
+        "  version: 1.0.0\n",
+        "workflows:\n",
+        "  - workflowId: workflowFoo\n",
+        "    steps:\n",
+        "      - stepId: stepFoo\n",
+        "        operationId: getFoo\n",
+        "        successCriteria:\n",
+        "          - condition: $statusCode == 201\n",
+        "        onFailure:\n",
+        "          - name: retryFoo\n",
+        "            type: retry\n",
+        "            retryLimit: 2\n",
+        "      - stepId: stepQux\n",
+        "        operationId: getQux\n",
+        "  - workflowId: workflowQux\n",
+        "    steps:\n",
+        "      - stepId: stepQux\n",
+        "        operationId: getQux\n",
+    );
+
+    #[test]
+    fn compare_documents() {
+        let old: ArazzoDocument = yaml_serde::from_str(OLD).unwrap();
+        let new: ArazzoDocument = yaml_serde::from_str(NEW).unwrap();
+
+        let actual = to_text(&Diff::compare(&old, &new));
+
+        let expected = concat!(
+            "~ workflowFoo.stepFoo successCriteria: $statusCode == 200 -> $statusCode == 201\n",
+            "+ workflowFoo.stepFoo onFailure.retryFoo: retry up to 2 times\n",
+            "+ workflowFoo.stepQux\n",
+            "- workflowFoo.stepBar\n",
+            "+ workflowQux\n",
+            "- workflowBar\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn compare_reordered_steps() {
+        let workflow = |steps: [&str; 3]| {
+            let steps = steps.map(|step_id| format!("      - stepId: {step_id}\n")).concat();
+            let content = format!(
+                concat!(
+                    "info:\n",
+                    "  title: Workflows\n",
+                    "workflows:\n",
+                    "  - workflowId: workflowFoo\n",
+                    "    steps:\n",
+                    "{}",
+                ),
+                steps,
+            );
+            yaml_serde::from_str::<ArazzoDocument>(&content).unwrap()
+        };
+        let old = workflow(["stepFoo", "stepBar", "stepBaz"]);
+        let new = workflow(["stepBar", "stepFoo", "stepBaz"]);
+
+        let actual = to_text(&Diff::compare(&old, &new));
+
+        let expected = concat!(
+            "~ workflowFoo.stepBar order: then stepBaz -> then stepFoo\n",
+            "~ workflowFoo.stepFoo order: then stepBar -> then stepBaz\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_diff() {
+        let old: ArazzoDocument = yaml_serde::from_str(OLD).unwrap();
+        let new: ArazzoDocument = yaml_serde::from_str(NEW).unwrap();
+        let sut = MermaidFlowchart::builder().diff(Diff::compare(&old, &new)).build();
+
+        let actual = sut.render(&merge(old, new));
+
+        let expected = concat!(
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo --> workflowFoo_stepFooNode{$statusCode == 201}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 201} -->|true| workflowFoo_stepBar\n",
+            "    workflowFoo_stepFooNode{$statusCode == 201} -->|false| workflowFoo_stepFoo_retryFooRetryNode([\"Retry up to 2 times\"])\n",
+            "    workflowFoo_stepFoo_retryFooRetryNode([\"Retry up to 2 times\"]) --> workflowFoo_stepFoo\n",
+            "    workflowFoo_stepBar --> workflowFoo_stepQux\n",
+            "    workflowFoo_stepQux --> workflowFooEndNode((End))\n",
+            "    end\n",
+            "    subgraph workflowQux\n",
+            "    workflowQux_stepQux --> workflowQuxEndNode((End))\n",
+            "    end\n",
+            "    subgraph workflowBar\n",
+            "    workflowBar_stepBaz --> workflowBarEndNode((End))\n",
+            "    end\n",
+            "    classDef added fill:#e6f4ea,stroke:#1e8e3e,stroke-width:2px\n",
+            "    classDef removed fill:#fce8e6,stroke:#d93025,stroke-width:2px,stroke-dasharray:4 2\n",
+            "    classDef changed fill:#fef7e0,stroke:#f9ab00,stroke-width:2px\n",
+            "    class workflowFoo_stepFoo_retryFooRetryNode,workflowFoo_stepQux,workflowQux,workflowQux_stepQux added\n",
+            "    class workflowFoo_stepBar,workflowBar,workflowBar_stepBaz removed\n",
+            "    class workflowFoo_stepFoo,workflowFoo_stepFooNode changed\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::arazzo::{Action, ArazzoDocument, Step, Workflow};
+use crate::renderer::{
+    MermaidFlowchart, Renderer, describe_action, describe_criteria, describe_operation,
+    describe_value,
+};
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2rem; }
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { border: 1px solid #ccc; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }
+th { background: #f5f5f5; }
+ul.cell { margin: 0; padding-left: 1rem; }
+";
+
+/// Renders a self-contained HTML report with mermaid.js inlined.
+pub struct HtmlReport {
+    pub flowchart: MermaidFlowchart,
+    pub mermaid_js: String,
+}
+
+impl Renderer for HtmlReport {
+    fn render(&self, arazzo: &ArazzoDocument) -> String {
+        let mut output = String::from("<!DOCTYPE html>\n");
+        output.push_str("<html>\n");
+        output.push_str("<head>\n");
+        output.push_str("<meta charset=\"utf-8\">\n");
+        output.push_str(&format!("<title>{}</title>\n", escape(&arazzo.info.title)));
+        output.push_str(&format!("<style>\n{STYLE}</style>\n"));
+        output.push_str("</head>\n");
+        output.push_str("<body>\n");
+        output.push_str(&format!("<h1>{}</h1>\n", escape(&arazzo.info.title)));
+
+        let workflows = self.flowchart.selected_workflows(arazzo);
+
+        output.push_str(&table_of_contents(&workflows));
+
+        output.push_str(&format!(
+            "<pre class=\"mermaid\">\n{}</pre>\n",
+            escape(&self.flowchart.render(arazzo)),
+        ));
+
+        for workflow in workflows {
+            output.push_str(&workflow_section(arazzo, workflow));
+        }
+
+        output.push_str(&format!(
+            "<script>\n{}\n</script>\n",
+            self.mermaid_js.replace("</script", "<\\/script"),
+        ));
+        output.push_str("<script>\nmermaid.initialize({ startOnLoad: true });\n</script>\n");
+        output.push_str("</body>\n");
+        output.push_str("</html>\n");
+
+        output
+    }
+}
+
+fn table_of_contents(workflows: &[&Workflow]) -> String {
+    let mut output = String::from("<nav>\n<ul>\n");
+
+    for workflow in workflows {
+        output.push_str(&format!(
+            "<li><a href=\"#{id}\">{id}</a></li>\n",
+            id = escape(&workflow.workflow_id),
+        ));
+    }
+
+    output.push_str("</ul>\n</nav>\n");
+
+    output
+}
+
+fn workflow_section(arazzo: &ArazzoDocument, workflow: &Workflow) -> String {
+    let mut output = format!(
+        "<section id=\"{id}\">\n<h2>{id}</h2>\n",
+        id = escape(&workflow.workflow_id),
+    );
+
+    if let Some(description) = workflow.description.as_deref() {
+        output.push_str(&format!("<p>{}</p>\n", escape(description)));
+    }
+
+    output.push_str("<table>\n");
+    output.push_str(concat!(
+        "<tr><th>Step</th><th>Operation</th><th>Parameters</th><th>Success Criteria</th>",
+        "<th>Outputs</th><th>On Success</th><th>On Failure</th></tr>\n",
+    ));
+
+    for step in &workflow.steps {
+        output.push_str(&step_row(arazzo, step));
+    }
+
+    output.push_str("</table>\n</section>\n");
+
+    output
+}
+
+fn step_row(arazzo: &ArazzoDocument, step: &Step) -> String {
+    let step_cell = match step.description.as_deref() {
+        Some(description) => format!(
+            "<code>{}</code><br>{}",
+            escape(&step.step_id),
+            escape(description),
+        ),
+        None => format!("<code>{}</code>", escape(&step.step_id)),
+    };
+
+    let operation = describe_operation(step).map_or(String::new(), |v| {
+        format!("<code>{}</code>", escape(v))
+    });
+
+    let parameters = step.parameters.as_deref().map_or(String::new(), |parameters| {
+        list(parameters.iter().map(|p| match arazzo.resolve_parameter(p) {
+            Some(p) => match p.location.as_deref() {
+                Some(location) => {
+                    format!("{} ({}): {}", p.name, location, describe_value(&p.value))
+                }
+                None => format!("{}: {}", p.name, describe_value(&p.value)),
+            },
+            None => format!("{} (unresolved)", p.name()),
+        }))
+    });
+
+    let success_criteria = step
+        .success_criteria
+        .as_deref()
+        .map_or(String::new(), |criteria| escape(&describe_criteria(criteria)));
+
+    let outputs = step.outputs.as_ref().map_or(String::new(), |outputs| {
+        list(outputs.iter().map(|(name, value)| format!("{name}: {value}")))
+    });
+
+    format!(
+        "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+        step_cell,
+        operation,
+        parameters,
+        success_criteria,
+        outputs,
+        actions(step.on_success.as_deref()),
+        actions(step.on_failure.as_deref()),
+    )
+}
+
+fn actions(actions: Option<&[Action]>) -> String {
+    actions.map_or(String::new(), |actions| list(actions.iter().map(describe_action)))
+}
+
+fn list(items: impl Iterator<Item = String>) -> String {
+    let items = items
+        .map(|item| format!("<li>{}</li>", escape(&item)))
+        .collect::<String>();
+
+    format!("<ul class=\"cell\">{items}</ul>")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::{ActionType, Criteria, Info, Parameter, StepParameter};
+    use indexmap::IndexMap;
+
+    #[test]
+    fn render_report() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: None,
+                description: None,
+                version: None,
+            },
+            source_descriptions: None,
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: Some(String::from("Workflow foo's description.")),
+                inputs: None,
+                depends_on: None,
+                steps: vec![Step {
+                    step_id: String::from("stepFoo"),
+                    description: None,
+                    operation_id: Some(String::from("createFoo")),
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: Some(vec![StepParameter::Parameter(Parameter {
+                        name: String::from("fooId"),
+                        location: Some(String::from("path")),
+                        value: serde_json::Value::String(String::from("$inputs.fooId")),
+                    })]),
+                    request_body: None,
+                    success_criteria: Some(vec![Criteria {
+                        context: None,
+                        condition: Some(String::from("$statusCode < 300")),
+                        criteria_type: None,
+                    }]),
+                    on_success: Some(vec![Action {
+                        name: String::from("done"),
+                        action_type: ActionType::End,
+                        workflow_id: None,
+                        step_id: None,
+                        retry_after: None,
+                        retry_limit: None,
+                        criteria: None,
+                    }]),
+                    on_failure: None,
+                    outputs: Some(IndexMap::from([(
+                        String::from("fooId"),
+                        String::from("$response.body#/id"),
+                    )])),
+                }],
+                outputs: None,
+            }],
+        };
+
+        let sut = HtmlReport {
+            flowchart: MermaidFlowchart::builder().build(),
+            mermaid_js: String::from("var mermaid = {}; // </script>"),
+        };
+
+        let actual = sut.render(&arazzo);
+
+        assert!(actual.contains("<li><a href=\"#workflowFoo\">workflowFoo</a></li>\n"));
+        assert!(actual.contains("<pre class=\"mermaid\">\n---\ntitle: Workflows\n---\nflowchart TD\n"));
+        assert!(actual.contains(concat!(
+            "<tr><td><code>stepFoo</code></td>",
+            "<td><code>createFoo</code></td>",
+            "<td><ul class=\"cell\"><li>fooId (path): $inputs.fooId</li></ul></td>",
+            "<td>$statusCode &lt; 300</td>",
+            "<td><ul class=\"cell\"><li>fooId: $response.body#/id</li></ul></td>",
+            "<td><ul class=\"cell\"><li>end</li></ul></td>",
+            "<td></td></tr>\n",
+        )));
+        assert!(actual.contains("var mermaid = {}; // <\\/script>"));
+        assert!(!actual.contains("cdn"));
+    }
+
+    #[test]
+    fn render_report_reusable_parameters() {
+        let content = std::fs::read_to_string("fixtures/reusable-parameters.yml").unwrap();
+        let arazzo = crate::parse(&content).unwrap();
+        let sut = HtmlReport {
+            flowchart: MermaidFlowchart::builder().build(),
+            mermaid_js: String::new(),
+        };
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "<td><ul class=\"cell\">",
+            "<li>page (query): 2</li>",
+            "<li>pageSize (query): 20</li>",
+            "<li>status (query): available</li>",
+            "</ul></td>",
+        );
+        println!("{actual}");
+        assert!(actual.contains(expected));
+    }
+}
@@ -0,0 +1,401 @@
+use crate::arazzo::{Action, ArazzoDocument, SourceDescription, SourceDescriptionType, Step, Workflow};
+use crate::renderer::{
+    MermaidFlowchart, Renderer, WorkflowFilter, describe_action, describe_criteria,
+    describe_operation,
+};
+
+/// Renders a Markdown document describing every workflow alongside its own diagram.
+pub struct MarkdownDocument {
+    pub flowchart: MermaidFlowchart,
+}
+
+impl Renderer for MarkdownDocument {
+    fn render(&self, arazzo: &ArazzoDocument) -> String {
+        let mut output = format!("# {}\n\n", arazzo.info.title);
+
+        if let Some(version) = arazzo.info.version.as_deref() {
+            output.push_str(&format!("Version: {version}\n\n"));
+        }
+        if let Some(summary) = arazzo.info.summary.as_deref() {
+            output.push_str(&format!("{summary}\n\n"));
+        }
+        if let Some(description) = arazzo.info.description.as_deref() {
+            output.push_str(&format!("{}\n\n", description.trim_end()));
+        }
+
+        if let Some(source_descriptions) = arazzo.source_descriptions.as_deref() {
+            output.push_str(&source_descriptions_section(source_descriptions));
+        }
+
+        for workflow in self.flowchart.selected_workflows(arazzo) {
+            let filter = WorkflowFilter {
+                workflow_ids: vec![workflow.workflow_id.clone()],
+                depth: 0,
+            };
+            let diagram = self.flowchart.render_filtered(arazzo, &filter);
+            output.push_str(&workflow_section(workflow, &diagram));
+        }
+
+        output.truncate(output.trim_end().len());
+        output.push('\n');
+
+        output
+    }
+}
+
+fn source_descriptions_section(source_descriptions: &[SourceDescription]) -> String {
+    let mut output = String::from("## Source Descriptions\n\n");
+    output.push_str("| Name | URL | Type |\n");
+    output.push_str("|------|-----|------|\n");
+
+    for source_description in source_descriptions {
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            cell(&source_description.name),
+            cell(&source_description.url),
+            match source_description.source_type {
+                Some(SourceDescriptionType::OpenApi) => "openapi",
+                Some(SourceDescriptionType::Arazzo) => "arazzo",
+                None => "",
+            },
+        ));
+    }
+
+    output.push('\n');
+
+    output
+}
+
+fn workflow_section(workflow: &Workflow, diagram: &str) -> String {
+    let mut output = format!("## {}\n\n", workflow.workflow_id);
+
+    if let Some(summary) = workflow.summary.as_deref() {
+        output.push_str(&format!("{summary}\n\n"));
+    }
+    if let Some(description) = workflow.description.as_deref() {
+        output.push_str(&format!("{}\n\n", description.trim_end()));
+    }
+
+    output.push_str(&format!("```mermaid\n{diagram}```\n\n"));
+
+    if let Some(inputs) = &workflow.inputs {
+        output.push_str(&inputs_section(inputs));
+    }
+
+    output.push_str("### Steps\n\n");
+    output.push_str("| Step | Operation | Success Criteria | Outputs | On Success | On Failure |\n");
+    output.push_str("|------|-----------|------------------|---------|------------|------------|\n");
+
+    for step in &workflow.steps {
+        output.push_str(&step_row(step));
+    }
+
+    output.push('\n');
+
+    if let Some(outputs) = &workflow.outputs {
+        output.push_str("### Outputs\n\n");
+        output.push_str("| Name | Value |\n");
+        output.push_str("|------|-------|\n");
+
+        for (name, value) in outputs {
+            output.push_str(&format!("| {} | {} |\n", cell(name), code(value)));
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+fn inputs_section(inputs: &serde_json::Value) -> String {
+    let mut output = String::from("### Inputs\n\n");
+
+    let Some(properties) = inputs.get("properties").and_then(|v| v.as_object()) else {
+        output.push_str(&format!("```json\n{inputs}\n```\n\n"));
+        return output;
+    };
+
+    let required = inputs
+        .get("required")
+        .and_then(|v| v.as_array())
+        .map_or(Vec::new(), |v| v.iter().filter_map(|v| v.as_str()).collect());
+
+    output.push_str("| Name | Type | Required | Description |\n");
+    output.push_str("|------|------|----------|-------------|\n");
+
+    for (name, schema) in properties {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            cell(name),
+            schema.get("type").and_then(|v| v.as_str()).unwrap_or_default(),
+            if required.contains(&name.as_str()) { "yes" } else { "no" },
+            cell(schema.get("description").and_then(|v| v.as_str()).unwrap_or_default()),
+        ));
+    }
+
+    output.push('\n');
+
+    output
+}
+
+fn step_row(step: &Step) -> String {
+    let step_cell = match step.description.as_deref() {
+        Some(description) => format!("`{}`<br>{}", step.step_id, cell(description)),
+        None => format!("`{}`", step.step_id),
+    };
+
+    let success_criteria = step
+        .success_criteria
+        .as_deref()
+        .map_or(String::new(), |criteria| code(&describe_criteria(criteria)));
+
+    let outputs = step.outputs.as_ref().map_or(String::new(), |outputs| {
+        outputs
+            .iter()
+            .map(|(name, value)| format!("{}: {}", cell(name), code(value)))
+            .collect::<Vec<String>>()
+            .join("<br>")
+    });
+
+    format!(
+        "| {} | {} | {} | {} | {} | {} |\n",
+        step_cell,
+        describe_operation(step).map_or(String::new(), code),
+        success_criteria,
+        outputs,
+        actions(step.on_success.as_deref()),
+        actions(step.on_failure.as_deref()),
+    )
+}
+
+fn actions(actions: Option<&[Action]>) -> String {
+    actions.map_or(String::new(), |actions| {
+        actions
+            .iter()
+            .map(|action| cell(&describe_action(action)))
+            .collect::<Vec<String>>()
+            .join("<br>")
+    })
+}
+
+fn code(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("`{}`", cell(text))
+    }
+}
+
+fn cell(text: &str) -> String {
+    text.trim().replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::{ActionType, Criteria, Info};
+    use indexmap::IndexMap;
+
+    #[test]
+    fn render_document() {
+        let arazzo = ArazzoDocument {
+            info: Info {
+                title: String::from("Workflows"),
+                summary: Some(String::from("Workflows' summary.")),
+                description: None,
+                version: Some(String::from("1.0.0")),
+            },
+            source_descriptions: Some(vec![SourceDescription {
+                name: String::from("fooApi"),
+                url: String::from("./openapi.yml"),
+                source_type: Some(SourceDescriptionType::OpenApi),
+            }]),
+            components: None,
+            workflows: vec![Workflow {
+                workflow_id: String::from("workflowFoo"),
+                summary: None,
+                description: Some(String::from("Workflow foo's description.")),
+                inputs: Some(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "amount": { "type": "number" },
+                        "note": { "type": "string", "description": "Free text." },
+                    },
+                    "required": ["amount"],
+                })),
+                depends_on: None,
+                steps: vec![Step {
+                    step_id: String::from("stepFoo"),
+                    description: Some(String::from("Step foo's description.")),
+                    operation_id: Some(String::from("createFoo")),
+                    operation_path: None,
+                    workflow_id: None,
+                    parameters: None,
+                    request_body: None,
+                    success_criteria: Some(vec![Criteria {
+                        context: None,
+                        condition: Some(String::from("$statusCode == 201")),
+                        criteria_type: None,
+                    }]),
+                    on_success: Some(vec![Action {
+                        name: String::from("done"),
+                        action_type: ActionType::End,
+                        workflow_id: None,
+                        step_id: None,
+                        retry_after: None,
+                        retry_limit: None,
+                        criteria: None,
+                    }]),
+                    on_failure: Some(vec![Action {
+                        name: String::from("retryFoo"),
+                        action_type: ActionType::Goto,
+                        workflow_id: None,
+                        step_id: Some(String::from("stepFoo")),
+                        retry_after: None,
+                        retry_limit: None,
+                        criteria: Some(vec![Criteria {
+                            context: None,
+                            condition: Some(String::from("$statusCode == 503")),
+                            criteria_type: None,
+                        }]),
+                    }]),
+                    outputs: Some(IndexMap::from([(
+                        String::from("fooId"),
+                        String::from("$response.body#/id"),
+                    )])),
+                }],
+                outputs: Some(IndexMap::from([(
+                    String::from("fooId"),
+                    String::from("$steps.stepFoo.outputs.fooId"),
+                )])),
+            }],
+        };
+
+        let sut = MarkdownDocument {
+            flowchart: MermaidFlowchart::builder().build(),
+        };
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "# Workflows\n",
+            "\n",
+            "Version: 1.0.0\n",
+            "\n",
+            "Workflows' summary.\n",
+            "\n",
+            "## Source Descriptions\n",
+            "\n",
+            "| Name | URL | Type |\n",
+            "|------|-----|------|\n",
+            "| fooApi | ./openapi.yml | openapi |\n",
+            "\n",
+            "## workflowFoo\n",
+            "\n",
+            "Workflow foo's description.\n",
+            "\n",
+            "```mermaid\n",
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo[\"Workflow foo's description.\"]\n",
+            "    workflowFoo_stepFoo[\"Step foo's description.\"] --> workflowFoo_stepFooNode{$statusCode == 201}\n",
+            "    workflowFoo_stepFooNode{$statusCode == 201} -->|true| workflowFooEndNode((End))\n",
+            "    workflowFoo_stepFooNode{$statusCode == 201} -->|false| workflowFoo_retryFooNode{$statusCode == 503}\n",
+            "    workflowFoo_retryFooNode{$statusCode == 503} -->|true| workflowFoo_stepFoo\n",
+            "    workflowFoo_retryFooNode{$statusCode == 503} -->|false| workflowFooEndNode((End))\n",
+            "    end\n",
+            "```\n",
+            "\n",
+            "### Inputs\n",
+            "\n",
+            "| Name | Type | Required | Description |\n",
+            "|------|------|----------|-------------|\n",
+            "| amount | number | yes |  |\n",
+            "| note | string | no | Free text. |\n",
+            "\n",
+            "### Steps\n",
+            "\n",
+            "| Step | Operation | Success Criteria | Outputs | On Success | On Failure |\n",
+            "|------|-----------|------------------|---------|------------|------------|\n",
+            "| `stepFoo`<br>Step foo's description. | `createFoo` | `$statusCode == 201` | fooId: `$response.body#/id` | end | goto stepFoo if $statusCode == 503 |\n",
+            "\n",
+            "### Outputs\n",
+            "\n",
+            "| Name | Value |\n",
+            "|------|-------|\n",
+            "| fooId | `$steps.stepFoo.outputs.fooId` |\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_diagram_per_workflow() {
+        let arazzo: ArazzoDocument = yaml_serde::from_str(concat!(
+            "info:\n",
+            "  title: Workflows\n",
+            "workflows:\n",
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        workflowId: workflowBar\n",
+            "  - workflowId: workflowBar\n",
+            "    steps:\n",
+            "      - stepId: stepBar\n",
+        ))
+        .unwrap();
+        let sut = MarkdownDocument {
+            flowchart: MermaidFlowchart::builder().build(),
+        };
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "# Workflows\n",
+            "\n",
+            "## workflowFoo\n",
+            "\n",
+            "```mermaid\n",
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowFoo\n",
+            "    workflowFoo_stepFoo[[\"workflowBar\"]] --> workflowFooEndNode((End))\n",
+            "    end\n",
+            "    workflowFoo_stepFoo -.-> workflowBar\n",
+            "    workflowBar([\"workflowBar\"])\n",
+            "```\n",
+            "\n",
+            "### Steps\n",
+            "\n",
+            "| Step | Operation | Success Criteria | Outputs | On Success | On Failure |\n",
+            "|------|-----------|------------------|---------|------------|------------|\n",
+            "| `stepFoo` | `workflowBar` |  |  |  |  |\n",
+            "\n",
+            "## workflowBar\n",
+            "\n",
+            "```mermaid\n",
+            "---\n",
+            "title: Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph workflowBar\n",
+            "    workflowBar_stepBar --> workflowBarEndNode((End))\n",
+            "    end\n",
+            "```\n",
+            "\n",
+            "### Steps\n",
+            "\n",
+            "| Step | Operation | Success Criteria | Outputs | On Success | On Failure |\n",
+            "|------|-----------|------------------|---------|------------|------------|\n",
+            "| `stepBar` |  |  |  |  |  |\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::arazzo::ArazzoDocument;
+use crate::renderer::{Edge, Graph, Verdict, should_branch, workflow_node_name};
+
+const TRACED_STYLE: &str = "stroke:#d93025,stroke-width:3px";
+const DIMMED_STYLE: &str = "opacity:0.35";
+
+/// Steps a workflow run went through, in the order they ran. Reads a list of
+/// events, or an object listing them under `steps` as `simulate` writes it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(from = "TraceFile")]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TraceFile {
+    Events(Vec<TraceEvent>),
+    Run { steps: Vec<TraceEvent> },
+}
+
+impl From<TraceFile> for Trace {
+    fn from(file: TraceFile) -> Self {
+        match file {
+            TraceFile::Events(events) | TraceFile::Run { steps: events } => Trace { events },
+        }
+    }
+}
+
+/// One step run. `workflowId` defaults to the first rendered workflow with the
+/// step, and `action` names the action taken when several share an outcome.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceEvent {
+    pub workflow_id: Option<String>,
+    pub step_id: String,
+    pub outcome: Option<TraceOutcome>,
+    pub action: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceOutcome {
+    Success,
+    Failure,
+}
+
+impl Trace {
+    /// Events naming a workflow or step the document does not declare.
+    pub fn unknown_steps(&self, arazzo: &ArazzoDocument) -> Vec<String> {
+        self.events
+            .iter()
+            .filter(|event| {
+                !arazzo.workflows.iter().any(|workflow| {
+                    event.workflow_id.as_ref().is_none_or(|id| id == &workflow.workflow_id)
+                        && workflow.steps.iter().any(|step| step.step_id == event.step_id)
+                })
+            })
+            .map(|event| match &event.workflow_id {
+                Some(workflow_id) => format!("{}.{}", workflow_id, event.step_id),
+                None => event.step_id.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Classes and `linkStyle`s highlighting the nodes and links the trace went
+/// through and dimming the others.
+pub(super) fn trace_styles(trace: &Trace, graphs: &[Graph], edges: &[Edge]) -> String {
+    let mut nodes: Vec<&str> = Vec::new();
+    for edge in edges {
+        for node_name in [edge.from.as_str(), edge.to.as_str()] {
+            if !nodes.contains(&node_name) {
+                nodes.push(node_name);
+            }
+        }
+    }
+
+    let step_nodes = graphs
+        .iter()
+        .flat_map(|(graph_id, _, workflow)| {
+            workflow.steps.iter().map(move |step| format!("{}_{}", graph_id, step.step_id))
+        })
+        .collect::<Vec<String>>();
+
+    let mut traced_nodes: Vec<String> = Vec::new();
+    let mut traced_links: Vec<usize> = Vec::new();
+
+    let resolved = trace
+        .events
+        .iter()
+        .filter_map(|event| resolve(event, graphs).map(|graph| (event, graph)))
+        .collect::<Vec<_>>();
+
+    for (i, (event, graph)) in resolved.iter().enumerate() {
+        let (graph_id, _, workflow) = graph;
+        let start = format!("{}_{}", graph_id, event.step_id);
+        let target = match resolved.get(i + 1) {
+            Some((next, (next_graph_id, _, _))) if next_graph_id == graph_id => {
+                format!("{}_{}", graph_id, next.step_id)
+            }
+            Some((_, (next_graph_id, _, _))) => next_graph_id.clone(),
+            None => format!("{}EndNode", graph_id),
+        };
+
+        let Some(step) = workflow.steps.iter().find(|step| step.step_id == event.step_id) else {
+            continue;
+        };
+        let decision = should_branch(step).then(|| format!("{start}Node"));
+        let actions = step
+            .on_success
+            .iter()
+            .filter(|_| event.outcome != Some(TraceOutcome::Failure))
+            .chain(step.on_failure.iter().filter(|_| event.outcome != Some(TraceOutcome::Success)))
+            .flatten()
+            .filter(|action| action.criteria.is_some())
+            .map(|action| (format!("{}_{}Node", graph_id, action.name), &action.name))
+            .collect::<Vec<(String, &String)>>();
+
+        let allowed = |edge: &Edge| -> bool {
+            if edge.dotted {
+                return false;
+            }
+            let verdict = edge.branch.map(|branch| branch.verdict);
+            if decision.as_ref() == Some(&edge.from) {
+                return match event.outcome {
+                    Some(TraceOutcome::Success) => verdict == Some(Verdict::Ok),
+                    Some(TraceOutcome::Failure) => verdict == Some(Verdict::Ng),
+                    None => true,
+                };
+            }
+            if let Some((_, name)) = actions.iter().find(|(node_name, _)| *node_name == edge.from)
+                && let Some(action) = event.action.as_ref()
+            {
+                return verdict == Some(if action == *name { Verdict::Ok } else { Verdict::Ng });
+            }
+
+            true
+        };
+
+        push(&mut traced_nodes, start.clone());
+        for index in shortest_path(edges, &start, &target, &step_nodes, allowed) {
+            push(&mut traced_nodes, edges[index].to.clone());
+            if !traced_links.contains(&index) {
+                traced_links.push(index);
+            }
+        }
+
+        if let Some(called) = step.workflow_id.as_deref() {
+            let called = workflow_node_name(called);
+            for (index, edge) in edges.iter().enumerate() {
+                if edge.dotted && edge.from == start && edge.to == called {
+                    traced_links.push(index);
+                }
+            }
+        }
+    }
+
+    let (traced, dimmed): (Vec<&str>, Vec<&str>) =
+        nodes.iter().partition(|node_name| traced_nodes.iter().any(|n| n == *node_name));
+    traced_links.sort_unstable();
+    traced_links.dedup();
+    let dimmed_links = (0..edges.len())
+        .filter(|index| !traced_links.contains(index))
+        .collect::<Vec<usize>>();
+
+    let mut styles = format!("    classDef tracedNode {TRACED_STYLE}\n");
+    styles.push_str(&format!("    classDef dimmedNode {DIMMED_STYLE}\n"));
+    for (node_names, class_name) in [(traced, "tracedNode"), (dimmed, "dimmedNode")] {
+        if !node_names.is_empty() {
+            styles.push_str(&format!("    class {} {}\n", node_names.join(","), class_name));
+        }
+    }
+    for (indexes, style) in [(traced_links, TRACED_STYLE), (dimmed_links, DIMMED_STYLE)] {
+        if !indexes.is_empty() {
+            let indexes = indexes.iter().map(usize::to_string).collect::<Vec<String>>();
+            styles.push_str(&format!("    linkStyle {} {}\n", indexes.join(","), style));
+        }
+    }
+
+    styles
+}
+
+fn push(node_names: &mut Vec<String>, node_name: String) {
+    if !node_names.contains(&node_name) {
+        node_names.push(node_name);
+    }
+}
+
+fn resolve<'a, 'b>(event: &TraceEvent, graphs: &'a [Graph<'b>]) -> Option<&'a Graph<'b>> {
+    graphs.iter().find(|(_, _, workflow)| {
+        event.workflow_id.as_ref().is_none_or(|id| id == &workflow.workflow_id)
+            && workflow.steps.iter().any(|step| step.step_id == event.step_id)
+    })
+}
+
+/// Link indexes of the shortest way from `start` to `target` that does not run
+/// through another step.
+fn shortest_path(
+    edges: &[Edge],
+    start: &str,
+    target: &str,
+    step_nodes: &[String],
+    allowed: impl Fn(&Edge) -> bool,
+) -> Vec<usize> {
+    let mut reached: Vec<(String, Option<usize>)> = vec![(start.to_string(), None)];
+    let mut queue: VecDeque<String> = VecDeque::from([start.to_string()]);
+
+    while let Some(node_name) = queue.pop_front() {
+        let is_step = step_nodes.contains(&node_name);
+        if node_name != start && is_step {
+            continue;
+        }
+
+        for (index, edge) in edges.iter().enumerate() {
+            if edge.from != node_name || !allowed(edge) {
+                continue;
+            }
+            if edge.to == target {
+                let mut path = vec![index];
+                let mut current = node_name.clone();
+                while let Some((_, Some(via))) = reached.iter().find(|(n, _)| n == &current) {
+                    path.push(*via);
+                    current = edges[*via].from.clone();
+                }
+                path.reverse();
+                return path;
+            }
+            if !reached.iter().any(|(n, _)| n == &edge.to) {
+                reached.push((edge.to.clone(), Some(index)));
+                queue.push_back(edge.to.clone());
+            }
+        }
+    }
+
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arazzo::ArazzoDocument;
+    use crate::renderer::{MermaidFlowchart, Renderer};
+
+    #[test]
+    fn render_trace() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let trace: Trace = serde_json::from_str(concat!(
+            "[",
+            "{\"stepId\": \"createAuthorizationStep\", \"outcome\": \"failure\"},",
+            "{\"stepId\": \"reverseAuthorizationStep\", \"outcome\": \"success\"}",
+            "]",
+        ))
+        .unwrap();
+        let sut = MermaidFlowchart::builder().trace(trace).build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "---\n",
+            "title: Credit Card API Workflows\n",
+            "---\n",
+            "flowchart TD\n",
+            "    subgraph authorizeThenCapture[\"Create a card authorization and then capture it.\"]\n",
+            "    authorizeThenCapture_createAuthorizationStep[\"Create a card authorization.\"] --> authorizeThenCapture_createAuthorizationStepNode{$statusCode == 201}\n",
+            "    authorizeThenCapture_createAuthorizationStepNode{$statusCode == 201} -->|true| authorizeThenCapture_captureAuthorizationStep\n",
+            "    authorizeThenCapture_createAuthorizationStepNode{$statusCode == 201} -->|false| authorizeThenCapture_reverseAuthorizationStep\n",
+            "    authorizeThenCapture_captureAuthorizationStep[\"Capture a card authorization.\"] --> authorizeThenCapture_captureAuthorizationStepNode{$statusCode == 201}\n",
+            "    authorizeThenCapture_captureAuthorizationStepNode{$statusCode == 201} -->|true| authorizeThenCaptureEndNode((End))\n",
+            "    authorizeThenCapture_captureAuthorizationStepNode{$statusCode == 201} -->|false| authorizeThenCapture_reverseAuthorizationStep\n",
+            "    authorizeThenCapture_reverseAuthorizationStep[\"Reverse a card authorization.\"] --> authorizeThenCapture_reverseAuthorizationStepNode{$statusCode == 201}\n",
+            "    authorizeThenCapture_reverseAuthorizationStepNode{$statusCode == 201} -->|true| authorizeThenCaptureEndNode((End))\n",
+            "    authorizeThenCapture_reverseAuthorizationStepNode{$statusCode == 201} -->|false| authorizeThenCaptureEndNode((End))\n",
+            "    end\n",
+            "    classDef tracedNode stroke:#d93025,stroke-width:3px\n",
+            "    classDef dimmedNode opacity:0.35\n",
+            "    class authorizeThenCapture_createAuthorizationStep,authorizeThenCapture_createAuthorizationStepNode,authorizeThenCapture_reverseAuthorizationStep,authorizeThenCaptureEndNode,authorizeThenCapture_reverseAuthorizationStepNode tracedNode\n",
+            "    class authorizeThenCapture_captureAuthorizationStep,authorizeThenCapture_captureAuthorizationStepNode dimmedNode\n",
+            "    linkStyle 0,2,6,7 stroke:#d93025,stroke-width:3px\n",
+            "    linkStyle 1,3,4,5,8 opacity:0.35\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn render_trace_through_action() {
+        let arazzo: ArazzoDocument = yaml_serde::from_str(concat!(
+            "info:\n",
+            "  title: Workflows\n",
+            "workflows:\n",
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        description: Send foo --> bar\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 200\n",
+            "        onFailure:\n",
+            "          - name: retryFoo\n",
+            "            type: retry\n",
+            "            criteria:\n",
+            "              - condition: $statusCode == 503\n",
+        ))
+        .unwrap();
+        let trace: Trace = serde_json::from_str(concat!(
+            "[",
+            "{\"stepId\": \"stepFoo\", \"outcome\": \"failure\", \"action\": \"retryFoo\"},",
+            "{\"stepId\": \"stepFoo\", \"outcome\": \"success\"}",
+            "]",
+        ))
+        .unwrap();
+        let sut = MermaidFlowchart::builder().trace(trace).build();
+
+        let actual = sut.render(&arazzo);
+
+        let expected = concat!(
+            "    class workflowFoo_stepFoo,workflowFoo_stepFooNode,workflowFooEndNode,workflowFoo_retryFooNode,workflowFoo_stepFoo_retryFooRetryNode tracedNode\n",
+            "    linkStyle 0,1,2,3,4 stroke:#d93025,stroke-width:3px\n",
+            "    linkStyle 5 opacity:0.35\n",
+        );
+
+        println!("{}", actual);
+        assert!(actual.ends_with(expected));
+    }
+}
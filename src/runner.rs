@@ -0,0 +1,394 @@
+use std::thread;
+use std::time::Duration;
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::arazzo::Step;
+use crate::openapi::OpenApiSources;
+use crate::operation::Operation;
+use crate::simulate::{MockResponse, Responder, SimulationError};
+
+/// A request ready to be sent for a step.
+#[derive(Debug, PartialEq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// Sends the steps' operations as real HTTP requests, to `base_url` when given,
+/// otherwise to the first server of the operation's OpenAPI description.
+pub struct HttpResponder<'a> {
+    sources: &'a OpenApiSources,
+    base_url: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl HttpResponder<'_> {
+    pub fn new(
+        sources: &OpenApiSources,
+        base_url: Option<String>,
+        timeout: Duration,
+    ) -> HttpResponder<'_> {
+        let config = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(timeout))
+            .build();
+
+        HttpResponder {
+            sources,
+            base_url,
+            agent: ureq::Agent::new_with_config(config),
+        }
+    }
+
+    fn send(&self, request: HttpRequest) -> Result<MockResponse, SimulationError> {
+        let failed = |error: ureq::Error| {
+            SimulationError(format!("{} {} failed: {}", request.method, request.url, error))
+        };
+
+        let mut builder = ureq::http::Request::builder()
+            .method(request.method.as_str())
+            .uri(request.url.as_str());
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        let response = match &request.body {
+            Some(body) => builder.body(body.as_str()).map(|r| self.agent.run(r)),
+            None => builder.body(()).map(|r| self.agent.run(r)),
+        }
+        .map_err(|error| failed(error.into()))?
+        .map_err(failed)?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (title_case(name.as_str()), String::from_utf8_lossy(value.as_bytes()).into())
+            })
+            .collect::<IndexMap<String, String>>();
+        let text = response.into_body().read_to_string().map_err(failed)?;
+        let body = match text.trim() {
+            "" => Value::Null,
+            trimmed => serde_json::from_str(trimmed).unwrap_or(Value::String(text)),
+        };
+
+        Ok(MockResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+impl Responder for HttpResponder<'_> {
+    fn respond(
+        &mut self,
+        workflow_id: &str,
+        step: &Step,
+        request: &Value,
+    ) -> Result<MockResponse, SimulationError> {
+        let unresolved = || {
+            SimulationError(format!(
+                "cannot resolve the operation of step '{}.{}'",
+                workflow_id, step.step_id
+            ))
+        };
+
+        let operation = Operation::from_step(step).ok_or_else(unresolved)?;
+        let server = self.sources.resolve(&operation).and_then(|o| o.server.clone());
+        let operation = self.sources.enrich(operation);
+        let (Some(method), Some(path)) = (operation.method, operation.path) else {
+            return Err(unresolved());
+        };
+        let base_url = self.base_url.clone().or(server).ok_or_else(|| {
+            SimulationError(format!(
+                "no base URL for step '{}.{}': pass --base-url or declare servers",
+                workflow_id, step.step_id
+            ))
+        })?;
+
+        self.send(build_request(&base_url, &method, &path, request))
+    }
+
+    fn wait(&mut self, seconds: f64) {
+        if let Ok(duration) = Duration::try_from_secs_f64(seconds) {
+            thread::sleep(duration);
+        }
+    }
+}
+
+/// Fills the path template from `$request.path`, appends `$request.query` and
+/// sends `$request.header`, `$request.cookie` as a `Cookie` header and
+/// `$request.body`, JSON unless told otherwise.
+pub fn build_request(base_url: &str, method: &str, path: &str, request: &Value) -> HttpRequest {
+    let mut url = base_url.trim_end_matches('/').to_string();
+    let mut rest = path;
+    while let Some((before, after)) = rest.split_once('{')
+        && let Some((name, after)) = after.split_once('}')
+    {
+        url.push_str(before);
+        url.push_str(&encode(&to_text(&request["path"][name])));
+        rest = after;
+    }
+    url.push_str(rest);
+
+    let query = request["query"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(name, value)| format!("{}={}", encode(name), encode(&to_text(value))))
+        .collect::<Vec<String>>();
+    if !query.is_empty() {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&query.join("&"));
+    }
+
+    let mut headers = request["header"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| (name.clone(), to_text(value)))
+        .collect::<Vec<(String, String)>>();
+    let cookies = request["cookie"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(name, value)| format!("{}={}", name, to_text(value)))
+        .collect::<Vec<String>>();
+    if !cookies.is_empty() {
+        headers.push((String::from("Cookie"), cookies.join("; ")));
+    }
+
+    let body = match &request["body"] {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        body => {
+            if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")) {
+                headers.push((String::from("Content-Type"), String::from("application/json")));
+            }
+            Some(body.to_string())
+        }
+    };
+
+    HttpRequest {
+        method: method.to_string(),
+        url,
+        headers,
+        body,
+    }
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// `content-type` becomes `Content-Type`, so `$response.header.Location` finds
+/// the header whatever case the server sent.
+fn title_case(name: &str) -> String {
+    name.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+    use std::thread::{self, JoinHandle};
+    use std::time::Instant;
+
+    use serde_json::{Map, json};
+
+    use super::*;
+    use crate::arazzo::ArazzoDocument;
+    use crate::simulate;
+
+    type Server = JoinHandle<Vec<(String, Instant)>>;
+
+    #[test]
+    fn build_request_from_parameters() {
+        let request = json!({
+            "header": { "X-Api-Key": "secret" },
+            "query": { "q": "a b&c", "limit": 10, "empty": null },
+            "path": { "id": "auth/1" },
+            "cookie": { "session": "abc", "theme": "dark" },
+            "body": { "amount": 100 },
+        });
+
+        let actual = build_request("http://localhost:4010/", "POST", "/items/{id}", &request);
+
+        let expected = HttpRequest {
+            method: String::from("POST"),
+            url: String::from("http://localhost:4010/items/auth%2F1?q=a%20b%26c&limit=10"),
+            headers: vec![
+                (String::from("X-Api-Key"), String::from("secret")),
+                (String::from("Cookie"), String::from("session=abc; theme=dark")),
+                (String::from("Content-Type"), String::from("application/json")),
+            ],
+            body: Some(String::from("{\"amount\":100}")),
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    /// Answers one connection per response on a local port, returning the base URL
+    /// and, once done, each request line with the time it arrived.
+    fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, Server) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                requests.push((request_line.trim().to_string(), Instant::now()));
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body,
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        (base_url, server)
+    }
+
+    #[test]
+    fn run_fixture_against_local_server() {
+        let (base_url, server) = serve(vec![
+            ("201 Created", "{\"id\":\"auth-1\",\"status\":\"authorized\"}"),
+            ("503 Service Unavailable", ""),
+            ("201 Created", "{\"id\":\"rev-1\",\"status\":\"reversed\"}"),
+        ]);
+
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let sources = OpenApiSources::load(&arazzo, Path::new("fixtures")).unwrap();
+        let mut sut = HttpResponder::new(&sources, Some(base_url), Duration::from_secs(5));
+
+        let actual = simulate::execute(&arazzo, None, &Map::new(), &mut sut).unwrap();
+
+        let steps = actual
+            .steps
+            .iter()
+            .map(|step| (step.step_id.as_str(), step.status_code, step.next.as_str()))
+            .collect::<Vec<(&str, Option<u16>, &str)>>();
+        let expected = vec![
+            ("createAuthorizationStep", Some(201), "captureAuthorizationStep"),
+            ("captureAuthorizationStep", Some(503), "reverseAuthorizationStep"),
+            ("reverseAuthorizationStep", Some(201), "end"),
+        ];
+
+        let requests = vec![
+            "POST /authorizations HTTP/1.1",
+            "POST /captures HTTP/1.1",
+            "POST /reversals HTTP/1.1",
+        ];
+
+        let actual_requests = server
+            .join()
+            .unwrap()
+            .into_iter()
+            .map(|(request_line, _)| request_line)
+            .collect::<Vec<String>>();
+
+        assert_eq!(expected, steps);
+        assert_eq!(requests, actual_requests);
+    }
+
+    #[test]
+    fn retry_waits_retry_after() {
+        let (base_url, server) = serve(vec![
+            ("503 Service Unavailable", ""),
+            ("201 Created", "{\"id\":\"auth-1\",\"status\":\"authorized\"}"),
+        ]);
+        let arazzo: ArazzoDocument = yaml_serde::from_str(concat!(
+            "info:\n",
+            "  title: Workflows\n",
+            "sourceDescriptions:\n",
+            "  - name: creditCardApi\n",
+            "    url: ./openapi.yml\n",
+            "    type: openapi\n",
+            "workflows:\n",
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        operationId: createAuthorization\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 201\n",
+            "        onFailure:\n",
+            "          - name: retryFoo\n",
+            "            type: retry\n",
+            "            retryAfter: 0.3\n",
+            "            retryLimit: 1\n",
+        ))
+        .unwrap();
+        let sources = OpenApiSources::load(&arazzo, Path::new("fixtures")).unwrap();
+        let mut sut = HttpResponder::new(&sources, Some(base_url), Duration::from_secs(5));
+
+        let actual = simulate::execute(&arazzo, None, &Map::new(), &mut sut).unwrap();
+
+        let steps = actual
+            .steps
+            .iter()
+            .map(|step| (step.status_code, step.next.as_str()))
+            .collect::<Vec<(Option<u16>, &str)>>();
+        let expected = vec![(Some(503), "stepFoo"), (Some(201), "end")];
+
+        let requests = server.join().unwrap();
+        let waited = requests[1].1.duration_since(requests[0].1);
+
+        assert_eq!(expected, steps);
+        assert!(waited >= Duration::from_millis(300), "retried after {waited:?}");
+    }
+}
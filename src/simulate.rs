@@ -0,0 +1,863 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::arazzo::{
+    Action, ArazzoDocument, Criteria, CriteriaTypeName, Parameter, Step, Transition, Workflow,
+};
+use crate::condition::Condition;
+use crate::expression::{self, Expression, TemplatePart};
+use crate::renderer::trace::TraceOutcome;
+
+/// Steps a simulation may run before it is treated as an endless loop.
+const MAX_STEPS: usize = 1000;
+
+/// Workflows a simulation may nest, through gotos and calls, before it is treated
+/// as an endless recursion; each one runs inside the workflow that reached it.
+const MAX_DEPTH: usize = 64;
+
+/// Mocked responses for a simulated run. Step responses are keyed by `stepId`,
+/// or `workflowId.stepId` when step IDs repeat across workflows.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scenario {
+    pub workflow_id: Option<String>,
+    #[serde(default)]
+    pub inputs: Map<String, Value>,
+    #[serde(default)]
+    pub steps: IndexMap<String, Responses>,
+}
+
+/// One response, or a list served in turn to repeated calls; the last one repeats.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Responses {
+    One(MockResponse),
+    Many(Vec<MockResponse>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: IndexMap<String, String>,
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// The trace of a simulated run. Its `steps` can be passed back as `--trace`.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Simulation {
+    pub workflow_id: String,
+    pub steps: Vec<SimulatedStep>,
+    pub end: String,
+    pub outputs: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedStep {
+    pub workflow_id: String,
+    pub step_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    pub outcome: TraceOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub outputs: Map<String, Value>,
+    pub next: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SimulationError(pub String);
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Simulation failed: {}", self.0)
+    }
+}
+
+impl Error for SimulationError {}
+
+/// How a workflow run finished: `None` when it ended, or why it failed.
+type WorkflowEnd = (Option<String>, Map<String, Value>);
+
+/// Answers the operation calls of a run.
+pub trait Responder {
+    /// Responds to `step`, given the `$request` built from its parameters and body.
+    fn respond(
+        &mut self,
+        workflow_id: &str,
+        step: &Step,
+        request: &Value,
+    ) -> Result<MockResponse, SimulationError>;
+
+    /// Waits `seconds` before a step is retried, as its `retryAfter` asks. Mocked
+    /// responses are answered at once.
+    fn wait(&mut self, _seconds: f64) {}
+}
+
+/// Serves the mocked responses of a scenario.
+struct Mocks<'a> {
+    scenario: &'a Scenario,
+    calls: HashMap<String, usize>,
+}
+
+impl Responder for Mocks<'_> {
+    fn respond(
+        &mut self,
+        workflow_id: &str,
+        step: &Step,
+        _request: &Value,
+    ) -> Result<MockResponse, SimulationError> {
+        let step_id = step.step_id.as_str();
+        let qualified = format!("{workflow_id}.{step_id}");
+        let Some((key, responses)) = [qualified.as_str(), step_id]
+            .into_iter()
+            .find_map(|key| self.scenario.steps.get(key).map(|responses| (key, responses)))
+        else {
+            return Err(SimulationError(format!("no mocked response for step '{qualified}'")));
+        };
+
+        let calls = self.calls.entry(key.to_string()).or_default();
+        let response = match responses {
+            Responses::One(response) => Some(response),
+            Responses::Many(responses) => responses.get(*calls).or(responses.last()),
+        };
+        *calls += 1;
+
+        response
+            .cloned()
+            .ok_or_else(|| SimulationError(format!("no mocked response for step '{qualified}'")))
+    }
+}
+
+/// Runs the scenario's workflow, or the first one, against its mocked responses.
+pub fn simulate(
+    arazzo: &ArazzoDocument,
+    scenario: &Scenario,
+) -> Result<Simulation, SimulationError> {
+    let mut mocks = Mocks {
+        scenario,
+        calls: HashMap::new(),
+    };
+
+    execute(arazzo, scenario.workflow_id.as_deref(), &scenario.inputs, &mut mocks)
+}
+
+/// Runs the given workflow, or the first one, taking its responses from `responder`.
+pub fn execute(
+    arazzo: &ArazzoDocument,
+    workflow_id: Option<&str>,
+    inputs: &Map<String, Value>,
+    responder: &mut dyn Responder,
+) -> Result<Simulation, SimulationError> {
+    let workflow = match workflow_id {
+        Some(workflow_id) => find_workflow(arazzo, workflow_id)?,
+        None => arazzo
+            .workflows
+            .first()
+            .ok_or_else(|| SimulationError(String::from("the document has no workflows")))?,
+    };
+
+    let mut simulator = Simulator {
+        arazzo,
+        responder,
+        steps: Vec::new(),
+        workflows: Map::new(),
+        depth: 0,
+    };
+    let (failure, outputs) = simulator.run(workflow, inputs.clone())?;
+
+    Ok(Simulation {
+        workflow_id: workflow.workflow_id.clone(),
+        steps: simulator.steps,
+        end: failure.map_or(String::from("end"), |reason| format!("failed: {reason}")),
+        outputs,
+    })
+}
+
+fn find_workflow<'a>(
+    arazzo: &'a ArazzoDocument,
+    workflow_id: &str,
+) -> Result<&'a Workflow, SimulationError> {
+    arazzo
+        .workflows
+        .iter()
+        .find(|workflow| workflow.workflow_id == workflow_id)
+        .ok_or_else(|| SimulationError(format!("workflow '{workflow_id}' not found")))
+}
+
+/// How one call of a step went.
+struct Attempt {
+    status_code: Option<u16>,
+    succeeded: bool,
+    outputs: Map<String, Value>,
+    /// Why the workflow the step calls failed.
+    called_failure: Option<String>,
+}
+
+struct Simulator<'a> {
+    arazzo: &'a ArazzoDocument,
+    responder: &'a mut dyn Responder,
+    steps: Vec<SimulatedStep>,
+    /// `$workflows.<id>.inputs` and `.outputs` of the workflows run so far.
+    workflows: Map<String, Value>,
+    /// Workflows currently running, shared by every nested run.
+    depth: usize,
+}
+
+impl Simulator<'_> {
+    fn run(
+        &mut self,
+        workflow: &Workflow,
+        inputs: Map<String, Value>,
+    ) -> Result<WorkflowEnd, SimulationError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(SimulationError(format!("stopped after {MAX_DEPTH} nested workflow runs")));
+        }
+        self.depth += 1;
+
+        let workflow_id = workflow.workflow_id.as_str();
+        let mut context = json!({ "inputs": inputs, "steps": {} });
+        let mut retries: HashMap<(usize, &str), u32> = HashMap::new();
+        let mut index = 0;
+
+        let failure = loop {
+            let Some(step) = workflow.steps.get(index) else {
+                break None;
+            };
+            if self.steps.len() >= MAX_STEPS {
+                return Err(SimulationError(format!("stopped after {MAX_STEPS} steps")));
+            }
+
+            let attempt = self.attempt(workflow_id, step, &mut context)?;
+            let actions = if attempt.succeeded { &step.on_success } else { &step.on_failure };
+            let mut action = None;
+            for candidate in actions.iter().flatten() {
+                if all_hold(candidate.criteria.as_deref().unwrap_or_default(), &context)? {
+                    action = Some(candidate);
+                    break;
+                }
+            }
+
+            let key = (index, action.map_or("", |action| action.name.as_str()));
+            let attempts = retries.get(&key).copied().unwrap_or_default();
+            let transition = workflow.transition(index, attempt.succeeded, action, attempts);
+            let next = match transition {
+                Transition::Step(target) => workflow.steps[target].step_id.clone(),
+                Transition::Workflow(target) => format!("workflow {target}"),
+                Transition::Retry { .. } => step.step_id.clone(),
+                Transition::End => String::from("end"),
+                Transition::Failed | Transition::RetryLimit => String::from("failed"),
+                Transition::UnknownStep(target) => {
+                    return Err(SimulationError(format!("step '{target}' not found")));
+                }
+            };
+            let called_failure = attempt.called_failure.clone();
+            self.record(workflow_id, step, attempt, action, next);
+
+            match transition {
+                Transition::Step(target) => index = target,
+                Transition::Workflow(target) => {
+                    break self.run(find_workflow(self.arazzo, target)?, Map::new())?.0;
+                }
+                // A retry first runs the workflow or step it names, if any, then
+                // waits its `retryAfter` before the step is tried again.
+                Transition::Retry {
+                    step: target,
+                    workflow: called,
+                } => {
+                    *retries.entry(key).or_default() += 1;
+                    if let Some(called) = called {
+                        self.run(find_workflow(self.arazzo, called)?, Map::new())?;
+                    }
+                    if let Some(target) = target {
+                        let target = &workflow.steps[target];
+                        let attempt = self.attempt(workflow_id, target, &mut context)?;
+                        self.record(workflow_id, target, attempt, None, step.step_id.clone());
+                    }
+                    if let Some(retry_after) = action.and_then(|action| action.retry_after) {
+                        self.responder.wait(retry_after);
+                    }
+                }
+                Transition::End => break None,
+                Transition::Failed => {
+                    break Some(
+                        called_failure.unwrap_or_else(|| format!("step '{}' failed", step.step_id)),
+                    );
+                }
+                Transition::RetryLimit => {
+                    let name = action.map_or("", |action| action.name.as_str());
+                    break Some(format!("retry limit of '{name}' reached"));
+                }
+                Transition::UnknownStep(_) => unreachable!("unknown steps fail the run above"),
+            }
+        };
+
+        context["workflows"] = Value::Object(self.workflows.clone());
+        let outputs = workflow
+            .outputs
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.clone(), resolve_text(value, &context)))
+            .collect::<Map<String, Value>>();
+        self.workflows.insert(
+            workflow_id.to_string(),
+            json!({ "inputs": context["inputs"], "outputs": outputs }),
+        );
+        self.depth -= 1;
+
+        Ok((failure, outputs))
+    }
+
+    /// Calls `step` once, through the responder or the workflow it names, and
+    /// keeps its outputs in `context` when it succeeds.
+    fn attempt(
+        &mut self,
+        workflow_id: &str,
+        step: &Step,
+        context: &mut Value,
+    ) -> Result<Attempt, SimulationError> {
+        context["workflows"] = Value::Object(self.workflows.clone());
+
+        let mut request = request(&parameters(self.arazzo, step)?, context);
+        if let Some(body) = &step.request_body {
+            if let Some(payload) = &body.payload {
+                request["body"] = resolve(payload, context);
+            }
+            if let Some(content_type) = &body.content_type {
+                request["header"]["Content-Type"] = json!(content_type);
+            }
+        }
+        context["request"] = request;
+
+        let (status_code, called_failure) = match step.workflow_id.as_deref() {
+            Some(called) => {
+                let inputs = parameters(self.arazzo, step)?
+                    .into_iter()
+                    .map(|p| (p.name.clone(), resolve(&p.value, context)))
+                    .collect();
+                let (failure, outputs) = self.run(find_workflow(self.arazzo, called)?, inputs)?;
+                context["workflows"] = Value::Object(self.workflows.clone());
+                context["outputs"] = Value::Object(outputs);
+                context["statusCode"] = Value::Null;
+                context["response"] = Value::Null;
+                (None, failure)
+            }
+            None => {
+                let response = self.responder.respond(workflow_id, step, &context["request"])?;
+                context["statusCode"] = json!(response.status);
+                context["response"] = json!({
+                    "header": response.headers,
+                    "body": response.body,
+                });
+                (Some(response.status), None)
+            }
+        };
+
+        let succeeded = called_failure.is_none()
+            && all_hold(step.success_criteria.as_deref().unwrap_or_default(), context)?;
+        let mut outputs = Map::new();
+        if succeeded {
+            for (name, value) in step.outputs.iter().flatten() {
+                outputs.insert(name.clone(), resolve_text(value, context));
+            }
+            context["steps"][&step.step_id] = json!({ "outputs": outputs });
+        }
+
+        Ok(Attempt {
+            status_code,
+            succeeded,
+            outputs,
+            called_failure,
+        })
+    }
+
+    fn record(
+        &mut self,
+        workflow_id: &str,
+        step: &Step,
+        attempt: Attempt,
+        action: Option<&Action>,
+        next: String,
+    ) {
+        self.steps.push(SimulatedStep {
+            workflow_id: workflow_id.to_string(),
+            step_id: step.step_id.clone(),
+            status_code: attempt.status_code,
+            outcome: if attempt.succeeded { TraceOutcome::Success } else { TraceOutcome::Failure },
+            action: action.map(|action| action.name.clone()),
+            outputs: attempt.outputs,
+            next,
+        });
+    }
+}
+
+/// The step parameters, with reusable ones resolved from `components.parameters`.
+fn parameters(arazzo: &ArazzoDocument, step: &Step) -> Result<Vec<Parameter>, SimulationError> {
+    step.parameters
+        .iter()
+        .flatten()
+        .map(|parameter| {
+            arazzo.resolve_parameter(parameter).ok_or_else(|| {
+                SimulationError(format!(
+                    "parameter '{}' of step '{}' not found in components",
+                    parameter.name(),
+                    step.step_id
+                ))
+            })
+        })
+        .collect()
+}
+
+/// `$request.header`, `.query`, `.path`, `.cookie` and `.body` built from the step
+/// parameters.
+fn request(parameters: &[Parameter], context: &Value) -> Value {
+    let mut request = json!({ "header": {}, "query": {}, "path": {}, "cookie": {} });
+    for parameter in parameters {
+        let location = match parameter.location.as_deref() {
+            Some("header") => "header",
+            Some("path") => "path",
+            Some("cookie") => "cookie",
+            _ => "query",
+        };
+        request[location][&parameter.name] = resolve(&parameter.value, context);
+    }
+
+    request
+}
+
+fn all_hold(criteria: &[Criteria], context: &Value) -> Result<bool, SimulationError> {
+    for criterion in criteria {
+        if !holds(criterion, context)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Evaluates a criterion: simple conditions against the whole context, regex and
+/// JSONPath conditions against the value of the criterion's `context`.
+fn holds(criterion: &Criteria, context: &Value) -> Result<bool, SimulationError> {
+    let condition = criterion.condition.as_deref().unwrap_or_default();
+    let criteria_type = expression::criteria_type_name(criterion);
+    if criteria_type == CriteriaTypeName::Simple {
+        return condition
+            .parse::<Condition>()
+            .and_then(|condition| condition.evaluate(context))
+            .map_err(|error| SimulationError(error.to_string()));
+    }
+
+    let value = criterion
+        .context
+        .as_deref()
+        .map(|text| resolve_text(text, context))
+        .ok_or_else(|| SimulationError(format!("criterion '{condition}' needs a context")))?;
+
+    match criteria_type {
+        CriteriaTypeName::Regex => {
+            let regex = regex::Regex::new(condition)
+                .map_err(|error| SimulationError(error.to_string()))?;
+            let text = match &value {
+                Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            Ok(regex.is_match(&text))
+        }
+        CriteriaTypeName::JsonPath => {
+            let path = serde_json_path::JsonPath::parse(condition)
+                .map_err(|error| SimulationError(error.to_string()))?;
+            Ok(!path.query(&value).all().is_empty())
+        }
+        _ => Err(SimulationError(format!("cannot evaluate xpath criterion '{condition}'"))),
+    }
+}
+
+fn resolve(value: &Value, context: &Value) -> Value {
+    match value {
+        Value::String(text) => resolve_text(text, context),
+        Value::Array(values) => values.iter().map(|value| resolve(value, context)).collect(),
+        Value::Object(values) => values
+            .iter()
+            .map(|(name, value)| (name.clone(), resolve(value, context)))
+            .collect::<Map<String, Value>>()
+            .into(),
+        value => value.clone(),
+    }
+}
+
+/// A whole runtime expression reads its value as is; a template embedding
+/// `{$...}` expressions is rendered into a string.
+fn resolve_text(text: &str, context: &Value) -> Value {
+    if text.starts_with('$') {
+        return match text.parse::<Expression>() {
+            Ok(expression) => lookup(&expression, context),
+            Err(_) => Value::String(text.to_string()),
+        };
+    }
+
+    let Ok(parts) = expression::parse_template(text) else {
+        return Value::String(text.to_string());
+    };
+    let rendered = parts
+        .iter()
+        .map(|part| match part {
+            TemplatePart::Text(text) => text.clone(),
+            TemplatePart::Expression(expression) => match lookup(expression, context) {
+                Value::String(text) => text,
+                value => value.to_string(),
+            },
+        })
+        .collect::<String>();
+
+    Value::String(rendered)
+}
+
+fn lookup(expression: &Expression, context: &Value) -> Value {
+    context.pointer(&expression.context_pointer()).cloned().unwrap_or(Value::Null)
+}
+
+/// One line per step, then how the run ended and its outputs.
+pub fn to_text(simulation: &Simulation) -> String {
+    let mut output = String::new();
+
+    for (i, step) in simulation.steps.iter().enumerate() {
+        let status_code = step.status_code.map_or(String::from("-"), |code| code.to_string());
+        let outcome = match step.outcome {
+            TraceOutcome::Success => "success",
+            TraceOutcome::Failure => "failure",
+        };
+        let action = step.action.as_ref().map_or(String::new(), |action| format!(" ({action})"));
+        output.push_str(&format!(
+            "{}. {}.{}: {} {}{} -> {}\n",
+            i + 1,
+            step.workflow_id,
+            step.step_id,
+            status_code,
+            outcome,
+            action,
+            step.next,
+        ));
+        if !step.outputs.is_empty() {
+            output.push_str(&format!("   outputs: {}\n", Value::Object(step.outputs.clone())));
+        }
+    }
+
+    output.push_str(&format!("{}: {}\n", simulation.workflow_id, simulation.end));
+    if !simulation.outputs.is_empty() {
+        output.push_str(&format!("outputs: {}\n", Value::Object(simulation.outputs.clone())));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(workflows: &str) -> ArazzoDocument {
+        let content = format!("info:\n  title: Workflows\nworkflows:\n{workflows}");
+        yaml_serde::from_str(&content).unwrap()
+    }
+
+    #[test]
+    fn request_by_location() {
+        let parameter = |name: &str, location: Option<&str>| Parameter {
+            name: String::from(name),
+            location: location.map(String::from),
+            value: Value::String(format!("{name} value")),
+        };
+        let parameters = [
+            parameter("X-Api-Key", Some("header")),
+            parameter("id", Some("path")),
+            parameter("session", Some("cookie")),
+            parameter("q", None),
+        ];
+
+        let actual = request(&parameters, &json!({}));
+
+        let expected = json!({
+            "header": { "X-Api-Key": "X-Api-Key value" },
+            "query": { "q": "q value" },
+            "path": { "id": "id value" },
+            "cookie": { "session": "session value" },
+        });
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn simulate_fixture() {
+        let content = std::fs::read_to_string("fixtures/arazzo.yml").unwrap();
+        let arazzo: ArazzoDocument = yaml_serde::from_str(&content).unwrap();
+        let content = std::fs::read_to_string("fixtures/scenario.yml").unwrap();
+        let scenario: Scenario = yaml_serde::from_str(&content).unwrap();
+
+        let actual = to_text(&simulate(&arazzo, &scenario).unwrap());
+
+        let expected = concat!(
+            "1. authorizeThenCapture.createAuthorizationStep: 201 success (proceedToCaptureAuthorizationStep) -> captureAuthorizationStep\n",
+            "   outputs: {\"authorizationId\":\"auth-1\",\"authorizationStatus\":\"authorized\"}\n",
+            "2. authorizeThenCapture.captureAuthorizationStep: 503 failure (captureAuthorizationStepFailure) -> reverseAuthorizationStep\n",
+            "3. authorizeThenCapture.reverseAuthorizationStep: 201 success (done) -> end\n",
+            "   outputs: {\"reversalId\":\"rev-1\",\"reversalStatus\":\"reversed\"}\n",
+            "authorizeThenCapture: end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn simulate_retry_and_outputs() {
+        let arazzo = document(concat!(
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        parameters:\n",
+            "          - name: id\n",
+            "            in: path\n",
+            "            value: $inputs.fooId\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 200 && $request.path.id == 'foo-1'\n",
+            "          - context: $response.header.Content-Type\n",
+            "            condition: ^application/json\n",
+            "            type: regex\n",
+            "          - context: $response.body\n",
+            "            condition: $[?@.ready == true]\n",
+            "            type: jsonpath\n",
+            "        onFailure:\n",
+            "          - name: retryFoo\n",
+            "            type: retry\n",
+            "            retryLimit: 2\n",
+            "            criteria:\n",
+            "              - condition: $statusCode == 503\n",
+            "        outputs:\n",
+            "          label: 'foo {$inputs.fooId} is {$response.body#/0/state}'\n",
+            "    outputs:\n",
+            "      label: $steps.stepFoo.outputs.label\n",
+        ));
+        let scenario: Scenario = yaml_serde::from_str(concat!(
+            "inputs:\n",
+            "  fooId: foo-1\n",
+            "steps:\n",
+            "  stepFoo:\n",
+            "    - status: 503\n",
+            "    - status: 200\n",
+            "      headers:\n",
+            "        Content-Type: application/json\n",
+            "      body:\n",
+            "        - ready: true\n",
+            "          state: ready\n",
+        ))
+        .unwrap();
+
+        let actual = simulate(&arazzo, &scenario).unwrap();
+
+        let expected = Simulation {
+            workflow_id: String::from("workflowFoo"),
+            steps: vec![
+                SimulatedStep {
+                    workflow_id: String::from("workflowFoo"),
+                    step_id: String::from("stepFoo"),
+                    status_code: Some(503),
+                    outcome: TraceOutcome::Failure,
+                    action: Some(String::from("retryFoo")),
+                    outputs: Map::new(),
+                    next: String::from("stepFoo"),
+                },
+                SimulatedStep {
+                    workflow_id: String::from("workflowFoo"),
+                    step_id: String::from("stepFoo"),
+                    status_code: Some(200),
+                    outcome: TraceOutcome::Success,
+                    action: None,
+                    outputs: Map::from_iter([(
+                        String::from("label"),
+                        json!("foo foo-1 is ready"),
+                    )]),
+                    next: String::from("end"),
+                },
+            ],
+            end: String::from("end"),
+            outputs: Map::from_iter([(String::from("label"), json!("foo foo-1 is ready"))]),
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn simulate_retry_through_step() {
+        let arazzo = document(concat!(
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        parameters:\n",
+            "          - name: Authorization\n",
+            "            in: header\n",
+            "            value: $steps.stepLogin.outputs.token\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 200 && $request.header.Authorization == 'token-1'\n",
+            "        onSuccess:\n",
+            "          - name: endFoo\n",
+            "            type: end\n",
+            "        onFailure:\n",
+            "          - name: loginFoo\n",
+            "            type: retry\n",
+            "            stepId: stepLogin\n",
+            "            retryAfter: 5\n",
+            "      - stepId: stepLogin\n",
+            "        outputs:\n",
+            "          token: $response.body#/token\n",
+        ));
+        let scenario: Scenario = yaml_serde::from_str(concat!(
+            "steps:\n",
+            "  stepFoo:\n",
+            "    - status: 401\n",
+            "    - status: 200\n",
+            "  stepLogin:\n",
+            "    status: 200\n",
+            "    body:\n",
+            "      token: token-1\n",
+        ))
+        .unwrap();
+
+        let actual = simulate(&arazzo, &scenario).unwrap();
+
+        let expected = Simulation {
+            workflow_id: String::from("workflowFoo"),
+            steps: vec![
+                SimulatedStep {
+                    workflow_id: String::from("workflowFoo"),
+                    step_id: String::from("stepFoo"),
+                    status_code: Some(401),
+                    outcome: TraceOutcome::Failure,
+                    action: Some(String::from("loginFoo")),
+                    outputs: Map::new(),
+                    next: String::from("stepFoo"),
+                },
+                SimulatedStep {
+                    workflow_id: String::from("workflowFoo"),
+                    step_id: String::from("stepLogin"),
+                    status_code: Some(200),
+                    outcome: TraceOutcome::Success,
+                    action: None,
+                    outputs: Map::from_iter([(String::from("token"), json!("token-1"))]),
+                    next: String::from("stepFoo"),
+                },
+                SimulatedStep {
+                    workflow_id: String::from("workflowFoo"),
+                    step_id: String::from("stepFoo"),
+                    status_code: Some(200),
+                    outcome: TraceOutcome::Success,
+                    action: Some(String::from("endFoo")),
+                    outputs: Map::new(),
+                    next: String::from("end"),
+                },
+            ],
+            end: String::from("end"),
+            outputs: Map::new(),
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn simulate_reusable_parameters() {
+        let arazzo = document(concat!(
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        parameters:\n",
+            "          - reference: $components.parameters.page\n",
+            "            value: 2\n",
+            "          - reference: $components.parameters.pageSize\n",
+            "        successCriteria:\n",
+            "          - condition: $request.query.page == 2 && $request.query.pageSize == 20\n",
+            "components:\n",
+            "  parameters:\n",
+            "    page:\n",
+            "      name: page\n",
+            "      in: query\n",
+            "      value: 1\n",
+            "    pageSize:\n",
+            "      name: pageSize\n",
+            "      in: query\n",
+            "      value: 20\n",
+        ));
+        let scenario: Scenario =
+            yaml_serde::from_str(concat!("steps:\n", "  stepFoo:\n", "    status: 200\n")).unwrap();
+
+        let actual = simulate(&arazzo, &scenario).unwrap().end;
+
+        assert_eq!("end", actual);
+    }
+
+    #[test]
+    fn simulate_failures() {
+        let arazzo = document(concat!(
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        successCriteria:\n",
+            "          - condition: $statusCode == 200\n",
+            "        onFailure:\n",
+            "          - name: retryFoo\n",
+            "            type: retry\n",
+        ));
+        let scenario = |steps: &str| -> Scenario { yaml_serde::from_str(steps).unwrap() };
+
+        let actual = [
+            simulate(&arazzo, &scenario("steps:\n  stepFoo:\n    status: 500\n")).map(|s| s.end),
+            simulate(&arazzo, &scenario("steps: {}\n")).map(|s| s.end),
+        ];
+
+        let expected = [
+            Ok(String::from("failed: retry limit of 'retryFoo' reached")),
+            Err(SimulationError(String::from(
+                "no mocked response for step 'workflowFoo.stepFoo'",
+            ))),
+        ];
+
+        assert_eq!(expected, actual);
+    }
+    #[test]
+    fn simulate_mutual_goto() {
+        let arazzo = document(concat!(
+            "  - workflowId: workflowFoo\n",
+            "    steps:\n",
+            "      - stepId: stepFoo\n",
+            "        onSuccess:\n",
+            "          - name: gotoBar\n",
+            "            type: goto\n",
+            "            workflowId: workflowBar\n",
+            "  - workflowId: workflowBar\n",
+            "    steps:\n",
+            "      - stepId: stepBar\n",
+            "        onSuccess:\n",
+            "          - name: gotoFoo\n",
+            "            type: goto\n",
+            "            workflowId: workflowFoo\n",
+        ));
+        let scenario: Scenario = yaml_serde::from_str(concat!(
+            "steps:\n",
+            "  stepFoo: { status: 200 }\n",
+            "  stepBar: { status: 200 }\n",
+        ))
+        .unwrap();
+
+        let actual = simulate(&arazzo, &scenario).map(|s| s.end);
+
+        let expected = Err(SimulationError(String::from("stopped after 64 nested workflow runs")));
+
+        assert_eq!(expected, actual);
+    }
+}
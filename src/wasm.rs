@@ -0,0 +1,143 @@
+use wasm_bindgen::prelude::*;
+
+use crate::renderer::{
+    ClassDefs, Direction, FlowchartOptions, MermaidFlowchart, Renderer, StepLabel,
+};
+use crate::ArazzoDocument;
+
+/// A parsed Arazzo document, kept on the Rust side between renders.
+#[wasm_bindgen]
+pub struct Document {
+    arazzo: ArazzoDocument,
+}
+
+#[wasm_bindgen]
+impl Document {
+    #[wasm_bindgen(getter)]
+    pub fn title(&self) -> String {
+        self.arazzo.info.title.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = workflowIds)]
+    pub fn workflow_ids(&self) -> Vec<String> {
+        self.arazzo.workflows.iter().map(|w| w.workflow_id.clone()).collect()
+    }
+}
+
+/// Parses an Arazzo document written in YAML.
+#[wasm_bindgen]
+pub fn parse(content: &str) -> Result<Document, JsError> {
+    let arazzo = crate::parse(content).map_err(|error| JsError::new(&error.to_string()))?;
+
+    Ok(Document { arazzo })
+}
+
+/// Parses an Arazzo document written in JSON.
+#[wasm_bindgen(js_name = parseJson)]
+pub fn parse_json(content: &str) -> Result<Document, JsError> {
+    let arazzo = crate::parse_json(content).map_err(|error| JsError::new(&error.to_string()))?;
+
+    Ok(Document { arazzo })
+}
+
+/// Renders every workflow of the document with the default options.
+#[wasm_bindgen]
+pub fn render(document: &Document) -> String {
+    crate::render(&document.arazzo)
+}
+
+/// Flowchart options set from JavaScript, mirroring the CLI flags.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Flowchart {
+    options: FlowchartOptions,
+    workflow_ids: Vec<String>,
+    depth: usize,
+}
+
+#[wasm_bindgen]
+impl Flowchart {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Flowchart {
+        Flowchart::default()
+    }
+
+    /// `TD`, `LR`, `BT` or `RL`.
+    #[wasm_bindgen(js_name = setDirection)]
+    pub fn set_direction(&mut self, direction: &str) -> Result<(), JsError> {
+        self.options.direction =
+            direction.parse::<Direction>().map_err(|error| JsError::new(&error))?;
+        Ok(())
+    }
+
+    /// `description`, `operation`, or a template such as `{stepId}: {operation}`.
+    #[wasm_bindgen(js_name = setStepLabel)]
+    pub fn set_step_label(&mut self, step_label: &str) {
+        self.options.step_label = StepLabel::from(step_label.to_string());
+    }
+
+    #[wasm_bindgen(js_name = setTheme)]
+    pub fn set_theme(&mut self, theme: &str) {
+        self.options.theme = Some(theme.to_string());
+    }
+
+    #[wasm_bindgen(js_name = setThemeVariable)]
+    pub fn set_theme_variable(&mut self, name: &str, value: &str) {
+        let theme_variables = self.options.theme_variables.get_or_insert_default();
+        theme_variables.insert(name.to_string(), value.to_string());
+    }
+
+    /// Applies the default `classDef` styles to every node.
+    #[wasm_bindgen(js_name = setStyled)]
+    pub fn set_styled(&mut self, styled: bool) {
+        self.options.class_defs = styled.then(ClassDefs::default);
+    }
+
+    /// Renders only the given workflows and those they reach within `depth` hops.
+    #[wasm_bindgen(js_name = setWorkflows)]
+    pub fn set_workflows(&mut self, workflow_ids: Vec<String>, depth: usize) {
+        self.workflow_ids = workflow_ids;
+        self.depth = depth;
+    }
+
+    pub fn render(&self, document: &Document) -> String {
+        let mut builder = MermaidFlowchart::builder().options(self.options.clone());
+        if !self.workflow_ids.is_empty() {
+            builder = builder.workflows(self.workflow_ids.clone(), self.depth);
+        }
+
+        builder.build().render(&document.arazzo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_with_options() {
+        let content = std::fs::read_to_string("fixtures/minimal.yml").unwrap();
+        let Ok(document) = parse(&content) else {
+            panic!("failed to parse fixture");
+        };
+        let mut sut = Flowchart::new();
+        assert!(sut.set_direction("lr").is_ok());
+        sut.set_theme("dark");
+
+        let actual = sut.render(&document);
+
+        let expected = concat!(
+            "---\n",
+            "title: t\n",
+            "---\n",
+            "%%{init: {\"theme\":\"dark\"}}%%\n",
+            "flowchart LR\n",
+            "    subgraph w\n",
+            "    w_s --> wEndNode((End))\n",
+            "    end\n",
+        );
+
+        println!("{}", actual);
+        assert_eq!(expected, actual);
+    }
+}